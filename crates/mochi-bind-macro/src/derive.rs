@@ -0,0 +1,185 @@
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Result, Type, WherePredicate,
+};
+
+struct Field {
+    ident: Ident,
+    key: String,
+    /// The type converted to and from a `PtrRef`, which is the inner type of
+    /// an `Option` field.
+    ty: Type,
+    /// Whether the field is an `Option`, which is left out of the object when
+    /// `None` and read as `None` when the key is missing.
+    optional: bool,
+}
+
+pub fn expand_into_host(input: TokenStream) -> Result<TokenStream> {
+    let mut input = syn::parse2::<DeriveInput>(input)?;
+    let fields = fields(&input, "IntoHost")?;
+    bound_params(&mut input, |param| parse_quote! { mochi::std::PtrRef: From<#param> });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // The conversions name the field type, otherwise the bounds added for
+    // generic parameters would be picked for every field.
+    let sets = fields.iter().map(|Field { ident, key, ty, optional }| {
+        if *optional {
+            quote! {
+                if let Some(field) = value.#ident {
+                    object.set(#key, <mochi::std::PtrRef as From<#ty>>::from(field));
+                }
+            }
+        } else {
+            quote! {
+                object.set(#key, <mochi::std::PtrRef as From<#ty>>::from(value.#ident));
+            }
+        }
+    });
+
+    Ok(
+        quote! {
+            impl #impl_generics From<#name #ty_generics> for mochi::std::PtrRef #where_clause {
+                fn from(value: #name #ty_generics) -> Self {
+                    let mut object = mochi::std::ObjectRef::new();
+                    #(#sets)*
+                    object.into()
+                }
+            }
+        }
+    )
+}
+
+pub fn expand_from_host(input: TokenStream) -> Result<TokenStream> {
+    let mut input = syn::parse2::<DeriveInput>(input)?;
+    let fields = fields(&input, "FromHost")?;
+    bound_params(&mut input, |param| parse_quote! { mochi::std::PtrRef: Into<#param> });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let reads = fields.iter().map(|Field { ident, key, ty, optional }| {
+        if *optional {
+            quote! {
+                #ident: {
+                    let field = object.get(#key);
+                    if field.is_some() {
+                        Some(<mochi::std::PtrRef as Into<#ty>>::into(field))
+                    } else {
+                        None
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #ident: <mochi::std::PtrRef as Into<#ty>>::into(object.get(#key))
+            }
+        }
+    });
+
+    // `Into` rather than `From`, like the conversions in `mochi::structs`.
+    Ok(
+        quote! {
+            #[allow(clippy::from_over_into)]
+            impl #impl_generics Into<#name #ty_generics> for mochi::std::PtrRef #where_clause {
+                fn into(self) -> #name #ty_generics {
+                    let object = self.as_object().unwrap_or_default();
+                    #name {
+                        #(#reads,)*
+                    }
+                }
+            }
+        }
+    )
+}
+
+fn bound_params(input: &mut DeriveInput, bound: impl Fn(&Ident) -> WherePredicate) {
+    let params: Vec<Ident> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in &params {
+        where_clause.predicates.push(bound(param));
+    }
+}
+
+fn fields(input: &DeriveInput, derive: &str) -> Result<Vec<Field>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(input.ident.span(), format!("[{}] requires named fields", derive))),
+        },
+        _ => return Err(Error::new(input.ident.span(), format!("[{}] can only be derived for structs", derive))),
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named fields have an ident");
+            let mut key = camel_case(&ident.to_string());
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("mochi")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        key = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown mochi attribute, expected `rename`"))
+                    }
+                })?;
+            }
+            Ok(match option_inner(&field.ty) {
+                Some(ty) => Field { ident, key, ty: ty.clone(), optional: true },
+                None => Field { ident, key, ty: field.ty.clone(), optional: false },
+            })
+        })
+        .collect()
+}
+
+/// The `T` of an `Option<T>` field.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `poster_image` becomes `posterImage`, the keys the host uses.
+fn camel_case(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let mut key = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !key.is_empty();
+        } else if upper {
+            key.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            key.push(c);
+        }
+    }
+    key
+}
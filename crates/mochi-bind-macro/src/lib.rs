@@ -2,10 +2,34 @@ extern crate proc_macro2;
 extern crate quote;
 extern crate syn;
 
+mod derive;
 mod parser;
+mod source;
+mod validate;
 
 use proc_macro::TokenStream;
 
+/// Exports the functions of an impl to the host, under their own names.
+///
+/// Implementations of `Meta`, `Video`, `Image`, `Text` and `Settings` are
+/// checked against the methods the host calls: each has to be there, with
+/// the same argument and return types, and nothing else.
+///
+/// Return values are passed to the host as an `i32` host pointer. The host
+/// calls these traits with host pointers, while other exports, such as
+/// helpers in an inherent impl, take their arguments as:
+///
+/// | Rust type | Wasm parameters |
+/// |---|---|
+/// | `&str`, `String` | `ptr: i32, len: i32`, a buffer from `mochi_alloc(len)` the export frees |
+/// | `Option<&str>`, `Option<String>` | `ptr: i32, len: i32`, with `len < 0` for `None` |
+/// | `i8`, `i16`, `i32`, `u8`, `u16`, `u32`, `isize`, `usize` | `i32` |
+/// | `i64`, `u64` | `i64` |
+/// | `f32`, `f64` | `f32`, `f64` |
+/// | `bool` | `i32`, `0` for `false` |
+/// | `Option` of a number or `bool` | `some: i32` (`0` for `None`), then the value |
+/// | any other `T` | `i32` host pointer, read with `Into<T> for PtrRef` |
+/// | any other `Option<T>` | `i32` host pointer, null for `None` |
 #[proc_macro_attribute]
 pub fn mochi_bind(
     attr: TokenStream, 
@@ -19,10 +43,57 @@ pub fn mochi_bind(
     }
 }
 
+/// Exports `source_info`, describing the source to the host.
+///
+/// Used on the type the source's traits are implemented for, which gets a
+/// `source_info()` function listing the traits it implements among `Meta`,
+/// `Video`, `Image`, `Text` and `Settings`. `name` is required, `id` and
+/// `version` default to the crate's name and version.
+///
+/// ```ignore
+/// #[mochi_source(name = "Zoro", base_url = "https://zoro.to", language = "en", icon = "https://zoro.to/icon.png", nsfw = false)]
+/// pub struct Zoro;
+/// ```
 #[proc_macro_attribute]
-pub fn mochi_test(
-    _attr: TokenStream, 
+pub fn mochi_source(
+    attr: TokenStream,
     input: TokenStream
 ) -> TokenStream {
-    input.into()
-}
\ No newline at end of file
+    match source::expand(attr.into(), input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            error.to_compile_error().into()
+        }
+    }
+}
+
+/// Implements `From<T> for PtrRef`, creating an object with a key per field.
+///
+/// Keys are the field names in camelCase, unless renamed with
+/// `#[mochi(rename = "key")]`. `Option` fields set to `None` are left out,
+/// fields of other types need `PtrRef: From<T>`, which covers primitives,
+/// `String`, `Vec<T>` and other `IntoHost` structs.
+#[proc_macro_derive(IntoHost, attributes(mochi))]
+pub fn into_host(input: TokenStream) -> TokenStream {
+    match derive::expand_into_host(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            error.to_compile_error().into()
+        }
+    }
+}
+
+/// Implements `Into<T> for PtrRef`, reading each field from an object with
+/// the same keys as [IntoHost](macro@IntoHost).
+///
+/// Missing keys are read as `None` for `Option` fields and as the default
+/// value otherwise.
+#[proc_macro_derive(FromHost, attributes(mochi))]
+pub fn from_host(input: TokenStream) -> TokenStream {
+    match derive::expand_from_host(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            error.to_compile_error().into()
+        }
+    }
+}
@@ -8,7 +8,12 @@ use alloc::{vec, format};
 use alloc::vec::Vec;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{parse_quote, punctuated::Punctuated, spanned::Spanned, Error, Result, ImplItem};
+use syn::{
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Error, GenericArgument, Ident, ImplItem,
+    PathArguments, Result, Type,
+};
+
+use crate::validate::{is_host_trait, validate};
 
 pub fn expand(_: TokenStream, input: TokenStream) -> Result<TokenStream> {
     let item = syn::parse2::<syn::Item>(input)?;
@@ -25,10 +30,25 @@ impl<'a> MacroParse<&'a mut TokenStream> for syn::Item {
     fn macro_parse(self, tokens: &'a mut TokenStream) -> Result<()> {
         match self {
             syn::Item::Impl(i) => {
+                validate(&i)?;
+
+                // Calls go through the trait, which may not be in scope.
+                let receiver = match &i.trait_ {
+                    Some((_, path, _)) => {
+                        let self_ty = &i.self_ty;
+                        quote! { <#self_ty as #path> }
+                    }
+                    None => quote! { Self },
+                };
+                // The host calls its traits with host pointers only.
+                let host_abi = i.trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|segment| is_host_trait(&segment.ident.to_string()));
                 let functions_used: Result<Vec<TokenStream>> = i.items
                     .iter()
                     .map(|o| {
-                        parse_impl_item(o)
+                        parse_impl_item(o, &receiver, host_abi)
                     })
                     .collect();
 
@@ -51,19 +71,19 @@ impl<'a> MacroParse<&'a mut TokenStream> for syn::Item {
                 }
             }
             _ => {
-                Err(Error::new(self.span(), "[mochi_bind] can only be used on `Meta`, `Video`, `Image`, and `Text` implementations."))
+                Err(Error::new(self.span(), "[mochi_bind] can only be used on `Meta`, `Video`, `Image`, `Text`, and `Settings` implementations."))
             }
         }
     }
 }
 
-fn parse_impl_item(item: &ImplItem) -> Result<TokenStream> {
+fn parse_impl_item(item: &ImplItem, receiver: &TokenStream, host_abi: bool) -> Result<TokenStream> {
     match item {
         ImplItem::Fn(f) => {
             let func_name = &f.sig.ident;
 
             let wasm_func_name = format_ident!("__wasm_{}", func_name.clone());
-            let wasm_export_name = format!("{}", func_name.to_string());
+            let wasm_export_name = func_name.to_string();
 
             let mut wasm_func_args = Punctuated::<syn::FnArg, syn::token::Comma>::new();
             let mut wasm_func_variables = vec![];
@@ -74,32 +94,28 @@ fn parse_impl_item(item: &ImplItem) -> Result<TokenStream> {
 
                 match arg {
                     syn::FnArg::Typed(syn::PatType { ty, .. }) => {
-                        wasm_func_calls.push(quote! { #ident });        
-                        match &**ty {
-                            _ => {
-                                wasm_func_args.push(parse_quote! {
-                                    #ident: i32
-                                });
-                                wasm_func_variables.push(quote! {
-                                    let #ident: #ty = mochi::std::PtrRef::new(#ident).into();
-                                });
-                            }
-                        }
+                        let abi = if host_abi { Abi::HostPtr } else { Abi::of(ty) };
+                        let (params, variable, call) = abi.expand(&ident, ty);
+                        wasm_func_args.extend(params);
+                        wasm_func_variables.push(variable);
+                        wasm_func_calls.push(call);
+                    }
+                    syn::FnArg::Receiver(self_arg) => {
+                        return Err(Error::new(self_arg.span(), "[mochi_bind] functions are called by the host without `self`, remove the receiver"));
                     }
-                    _ => todo!(),
                 }
             }
 
             let (wasm_func_call_block, wasm_ret_val) = match &f.sig.output {
                 syn::ReturnType::Default => (
                     quote! {
-                        Self::#func_name(#(#wasm_func_calls,)*)
+                        #receiver::#func_name(#(#wasm_func_calls,)*)
                     },
                     syn::ReturnType::Default,
                 ),
                 syn::ReturnType::Type(_, ty) => (
                     quote! {
-                        let ret_val: #ty = Self::#func_name(#(#wasm_func_calls,)*);
+                        let ret_val: #ty = #receiver::#func_name(#(#wasm_func_calls,)*);
                         let ptr_ref = mochi::std::PtrRef::from(ret_val);
                         let ret_ptr = ptr_ref.pointer();
                         core::mem::forget(ptr_ref);
@@ -113,17 +129,143 @@ fn parse_impl_item(item: &ImplItem) -> Result<TokenStream> {
 
             Ok(
                 quote! {
-                    #[no_mangle]
-                    #[export_name = #wasm_export_name]
+                    // Only exported on wasm, which is what keeps the function
+                    // and the method it calls from being dead code.
+                    #[cfg_attr(target_arch = "wasm32", export_name = #wasm_export_name)]
+                    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
                     extern "C" fn #wasm_func_name(#wasm_func_args) #wasm_ret_val {
-                        #(#wasm_func_variables;)*
+                        #(#wasm_func_variables)*
                         #wasm_func_call_block
                     }
                 }
             )
         },
         _ => {
-            return Err(Error::new(item.span(), "cannot use [mochi_bind] on non-func types"));
+            Err(Error::new(item.span(), "cannot use [mochi_bind] on non-func types"))
         },
     }
-}
\ No newline at end of file
+}
+/// How an argument is passed to an export.
+enum Abi {
+    /// `i32` host pointer, read with `Into<T> for PtrRef`.
+    HostPtr,
+    /// `i32` host pointer, null for `None`.
+    OptionHostPtr,
+    /// `(ptr: i32, len: i32)` buffer from `mochi_alloc`, `len < 0` for `None`.
+    Str { borrowed: bool, optional: bool },
+    /// Passed as the wasm type `wasm`, preceded by an `i32` that is `0` for
+    /// `None` when optional.
+    Primitive { wasm: TokenStream, bool: bool, optional: bool },
+}
+
+impl Abi {
+    fn of(ty: &Type) -> Self {
+        match option_inner(ty) {
+            Some(inner) => match Self::of_value(inner) {
+                Abi::HostPtr => Abi::OptionHostPtr,
+                Abi::Str { borrowed, .. } => Abi::Str { borrowed, optional: true },
+                Abi::Primitive { wasm, bool, .. } => Abi::Primitive { wasm, bool, optional: true },
+                abi => abi,
+            },
+            None => Self::of_value(ty),
+        }
+    }
+
+    fn of_value(ty: &Type) -> Self {
+        if let Type::Reference(reference) = ty {
+            return match &*reference.elem {
+                Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => {
+                    Abi::Str { borrowed: true, optional: false }
+                }
+                _ => Abi::HostPtr,
+            };
+        }
+        let Type::Path(path) = ty else {
+            return Abi::HostPtr;
+        };
+        if path.qself.is_some() {
+            return Abi::HostPtr;
+        }
+        if path.path.segments.last().is_some_and(|segment| segment.ident == "String" && segment.arguments.is_empty()) {
+            return Abi::Str { borrowed: false, optional: false };
+        }
+        let Some(ident) = path.path.get_ident() else {
+            return Abi::HostPtr;
+        };
+        let wasm = match ident.to_string().as_str() {
+            "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "isize" | "usize" | "bool" => quote! { i32 },
+            "i64" | "u64" => quote! { i64 },
+            "f32" => quote! { f32 },
+            "f64" => quote! { f64 },
+            _ => return Abi::HostPtr,
+        };
+        Abi::Primitive { wasm, bool: ident == "bool", optional: false }
+    }
+
+    /// The parameters of the export, the statement reading them into a
+    /// variable named `ident` and the expression passed to the function.
+    fn expand(&self, ident: &Ident, ty: &Type) -> (Vec<syn::FnArg>, TokenStream, TokenStream) {
+        match self {
+            Abi::HostPtr => (
+                vec![parse_quote! { #ident: i32 }],
+                quote! { let #ident: #ty = mochi::std::PtrRef::new(#ident).into(); },
+                quote! { #ident },
+            ),
+            Abi::OptionHostPtr => (
+                vec![parse_quote! { #ident: i32 }],
+                quote! {
+                    let #ident: #ty = {
+                        let ptr_ref = mochi::std::PtrRef::new(#ident);
+                        if ptr_ref.is_some() { Some(ptr_ref.into()) } else { None }
+                    };
+                },
+                quote! { #ident },
+            ),
+            Abi::Str { borrowed, optional } => {
+                let len = format_ident!("{}_len", ident);
+                let read = quote! { unsafe { mochi::abi::take_string(#ident, #len) } };
+                let variable = if *optional {
+                    quote! { let #ident = #read; }
+                } else {
+                    quote! { let #ident = #read.unwrap_or_default(); }
+                };
+                let call = match (borrowed, optional) {
+                    (true, true) => quote! { #ident.as_deref() },
+                    (true, false) => quote! { #ident.as_str() },
+                    (false, _) => quote! { #ident },
+                };
+                (vec![parse_quote! { #ident: i32 }, parse_quote! { #len: i32 }], variable, call)
+            }
+            Abi::Primitive { wasm, bool, optional } => {
+                let value_ty = option_inner(ty).unwrap_or(ty);
+                let value = if *bool { quote! { #ident != 0 } } else { quote! { #ident as #value_ty } };
+                let mut params: Vec<syn::FnArg> = vec![parse_quote! { #ident: #wasm }];
+                let variable = if *optional {
+                    let some = format_ident!("{}_some", ident);
+                    params.insert(0, parse_quote! { #some: i32 });
+                    quote! { let #ident: #ty = if #some != 0 { Some(#value) } else { None }; }
+                } else {
+                    quote! { let #ident: #ty = #value; }
+                };
+                (params, variable, quote! { #ident })
+            }
+        }
+    }
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if path.qself.is_some() || segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,138 @@
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::Parser;
+use syn::{Error, Item, LitBool, LitStr, Result};
+
+#[derive(Default)]
+struct Args {
+    id: Option<LitStr>,
+    name: Option<LitStr>,
+    version: Option<LitStr>,
+    icon: Option<LitStr>,
+    base_url: Option<LitStr>,
+    language: Option<LitStr>,
+    nsfw: Option<LitBool>,
+}
+
+fn parse_args(attr: TokenStream) -> Result<Args> {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| {
+        let value = |field: &mut Option<LitStr>| -> Result<()> {
+            *field = Some(meta.value()?.parse()?);
+            Ok(())
+        };
+        if meta.path.is_ident("id") {
+            value(&mut args.id)
+        } else if meta.path.is_ident("name") {
+            value(&mut args.name)
+        } else if meta.path.is_ident("version") {
+            value(&mut args.version)
+        } else if meta.path.is_ident("icon") {
+            value(&mut args.icon)
+        } else if meta.path.is_ident("base_url") {
+            value(&mut args.base_url)
+        } else if meta.path.is_ident("language") {
+            value(&mut args.language)
+        } else if meta.path.is_ident("nsfw") {
+            // `nsfw` alone means `nsfw = true`.
+            args.nsfw = Some(if meta.input.is_empty() || meta.input.peek(syn::Token![,]) {
+                LitBool::new(true, meta.path.get_ident().map(|i| i.span()).unwrap_or_else(Span::call_site))
+            } else {
+                meta.value()?.parse()?
+            });
+            Ok(())
+        } else {
+            Err(meta.error("unknown mochi_source argument, expected `id`, `name`, `version`, `icon`, `base_url`, `language` or `nsfw`"))
+        }
+    });
+    parser.parse2(attr)?;
+    Ok(args)
+}
+
+fn optional(value: Option<LitStr>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(alloc::string::String::from(#value)) },
+        None => quote! { None },
+    }
+}
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    let item = syn::parse2::<Item>(input)?;
+    let ident = match &item {
+        Item::Struct(item) if item.generics.params.is_empty() => &item.ident,
+        Item::Enum(item) if item.generics.params.is_empty() => &item.ident,
+        Item::Struct(syn::ItemStruct { generics, .. }) | Item::Enum(syn::ItemEnum { generics, .. }) => {
+            return Err(Error::new_spanned(generics, "[mochi_source] cannot be used on a generic type"));
+        }
+        _ => return Err(Error::new_spanned(&item, "[mochi_source] can only be used on the struct or enum of a source")),
+    };
+
+    let args = parse_args(attr)?;
+    let Some(name) = args.name else {
+        return Err(Error::new(Span::call_site(), "[mochi_source] requires a `name`, e.g. `#[mochi_source(name = \"Source\")]`"));
+    };
+    let id = match args.id {
+        Some(id) => quote! { #id },
+        None => quote! { env!("CARGO_PKG_NAME") },
+    };
+    let version = match args.version {
+        Some(version) => quote! { #version },
+        None => quote! { env!("CARGO_PKG_VERSION") },
+    };
+    let icon = optional(args.icon);
+    let base_url = optional(args.base_url);
+    let language = optional(args.language);
+    let nsfw = args.nsfw.map(|nsfw| nsfw.value).unwrap_or(false);
+
+    Ok(
+        quote! {
+            #item
+
+            impl #ident {
+                /// Generated by `#[mochi_source]`, with the traits this type implements.
+                pub fn source_info() -> mochi::structs::source::SourceInfo {
+                    extern crate alloc;
+                    use mochi::structs::source::Capability;
+                    use mochi::structs::source::probe::*;
+
+                    let probe = &Probe::<#ident>::new();
+                    let capabilities = [
+                        (probe.meta(), Capability::Meta),
+                        (probe.video(), Capability::Video),
+                        (probe.image(), Capability::Image),
+                        (probe.text(), Capability::Text),
+                        (probe.settings(), Capability::Settings),
+                    ];
+
+                    mochi::structs::source::SourceInfo {
+                        id: alloc::string::String::from(#id),
+                        name: alloc::string::String::from(#name),
+                        version: alloc::string::String::from(#version),
+                        icon: #icon,
+                        base_url: #base_url,
+                        language: #language,
+                        nsfw: #nsfw,
+                        capabilities: capabilities
+                            .into_iter()
+                            .filter(|(implemented, _)| *implemented)
+                            .map(|(_, capability)| capability)
+                            .collect(),
+                    }
+                }
+
+                #[cfg_attr(target_arch = "wasm32", export_name = "source_info")]
+                #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+                extern "C" fn __wasm_source_info() -> i32 {
+                    let ptr_ref = mochi::std::PtrRef::from(Self::source_info());
+                    let ret_ptr = ptr_ref.pointer();
+                    core::mem::forget(ptr_ref);
+                    ret_ptr
+                }
+            }
+        }
+    )
+}
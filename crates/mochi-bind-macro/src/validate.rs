@@ -0,0 +1,222 @@
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use quote::ToTokens;
+use syn::{spanned::Spanned, Error, FnArg, GenericArgument, ImplItem, ItemImpl, PathArguments, Result, ReturnType, Type};
+
+/// A method the host calls, with its argument and return types as written
+/// in `mochi::structs`.
+struct Signature {
+    name: &'static str,
+    args: &'static [&'static str],
+    output: &'static str,
+}
+
+impl Signature {
+    fn describe(&self) -> String {
+        format!("fn {}({}) -> {}", self.name, self.args.join(", "), self.output)
+    }
+}
+
+const fn sig(name: &'static str, args: &'static [&'static str], output: &'static str) -> Signature {
+    Signature { name, args, output }
+}
+
+const META: &[Signature] = &[
+    sig("search_filters", &[], "SearchFilters"),
+    sig("search", &["SearchQuery"], "Result<Paging<Playlist>>"),
+    sig("discover_listings", &[], "Result<DiscoverListings>"),
+    sig("playlist_details", &["String"], "Result<PlaylistDetails>"),
+];
+
+const VIDEO: &[Signature] = &[
+    sig("playlist_episodes", &["PlaylistItemsRequest"], "Result<PlaylistItemsResponse>"),
+    sig("playlist_episode_sources", &["PlaylistEpisodeSourcesRequest"], "Result<PlaylistEpisodeSources>"),
+    sig("playlist_episode_server", &["PlaylistEpisodeServerRequest"], "Result<PlaylistEpisodeServerResponse>"),
+];
+
+const IMAGE: &[Signature] = &[sig("playlist_images", &["PlaylistItemsRequest"], "PlaylistItemsResponse")];
+
+const TEXT: &[Signature] = &[sig("playlist_texts", &["PlaylistItemsRequest"], "PlaylistItemsResponse")];
+
+const SETTINGS: &[Signature] = &[sig("settings", &[], "SettingsSchema")];
+
+fn signatures(trait_name: &str) -> Option<&'static [Signature]> {
+    match trait_name {
+        "Meta" => Some(META),
+        "Video" => Some(VIDEO),
+        "Image" => Some(IMAGE),
+        "Text" => Some(TEXT),
+        "Settings" => Some(SETTINGS),
+        _ => None,
+    }
+}
+
+/// Whether `trait_name` is one of the traits the host calls.
+pub fn is_host_trait(trait_name: &str) -> bool {
+    signatures(trait_name).is_some()
+}
+
+/// Type aliases of `mochi::structs` and the type they stand for.
+fn resolve_alias(name: &str) -> &str {
+    match name {
+        "PlaylistID" | "PlaylistItemID" => "String",
+        name => name,
+    }
+}
+
+/// Compares types by the last segment of their paths, so both `Playlist`
+/// and `mochi::structs::meta::Playlist` match. Only the value type of a
+/// `Result` is compared, as the error may be spelled out.
+fn same_type(expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+        (_, Type::Paren(actual)) => same_type(expected, &actual.elem),
+        (_, Type::Group(actual)) => same_type(expected, &actual.elem),
+        (Type::Path(expected), Type::Path(actual)) if expected.qself.is_none() && actual.qself.is_none() => {
+            let (Some(expected), Some(actual)) = (expected.path.segments.last(), actual.path.segments.last()) else {
+                return false;
+            };
+            let name = expected.ident.to_string();
+            if resolve_alias(&name) != resolve_alias(&actual.ident.to_string()) {
+                return false;
+            }
+            let expected_args = type_args(&expected.arguments);
+            let actual_args = type_args(&actual.arguments);
+            if name == "Result" {
+                return matches!(actual_args.len(), 1 | 2) && same_type(expected_args[0], actual_args[0]);
+            }
+            expected_args.len() == actual_args.len()
+                && expected_args.iter().zip(&actual_args).all(|(expected, actual)| same_type(expected, actual))
+        }
+        _ => expected.to_token_stream().to_string() == actual.to_token_stream().to_string(),
+    }
+}
+
+fn type_args(arguments: &PathArguments) -> Vec<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_type(ty: &str) -> Type {
+    syn::parse_str(ty).expect("signatures are valid types")
+}
+
+fn push(errors: &mut Option<Error>, error: Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+/// Checks that the functions of `item` can be exported: none of them take
+/// `self`, are generic or async.
+///
+/// For implementations of `Meta`, `Video`, `Image`, `Text` and `Settings`,
+/// also checks that each method the host calls is there with the expected
+/// types, and that there are no others.
+pub fn validate(item: &ItemImpl) -> Result<()> {
+    let mut errors = None;
+    let trait_path = item.trait_.as_ref().map(|(_, path, _)| path);
+    let trait_name = trait_path
+        .and_then(|path| path.segments.last())
+        .map(|segment| segment.ident.to_string());
+    let expected = trait_name.as_deref().and_then(signatures);
+
+    for impl_item in &item.items {
+        let ImplItem::Fn(f) = impl_item else {
+            continue;
+        };
+        let sig = &f.sig;
+
+        if let Some(receiver) = sig.receiver() {
+            push(&mut errors, Error::new_spanned(receiver, "[mochi_bind] functions are called by the host without `self`, remove the receiver"));
+        }
+        if !sig.generics.params.is_empty() {
+            push(&mut errors, Error::new_spanned(&sig.generics, "[mochi_bind] functions cannot be generic"));
+        }
+        if let Some(asyncness) = sig.asyncness {
+            push(&mut errors, Error::new(asyncness.span(), "[mochi_bind] functions cannot be async"));
+        }
+
+        let (Some(trait_name), Some(expected)) = (&trait_name, expected) else {
+            continue;
+        };
+        let name = sig.ident.to_string();
+        let Some(signature) = expected.iter().find(|signature| signature.name == name) else {
+            let names: Vec<String> = expected.iter().map(|signature| format!("`{}`", signature.name)).collect();
+            push(&mut errors, Error::new(sig.ident.span(), format!("`{}` is not a method of `{}`, expected one of {}", name, trait_name, names.join(", "))));
+            continue;
+        };
+
+        let args: Vec<&Type> = sig
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(arg) => Some(&*arg.ty),
+                FnArg::Receiver(_) => None,
+            })
+            .collect();
+        if args.len() != signature.args.len() {
+            let message = format!(
+                "`{}` takes {} argument(s), expected `{}`",
+                name,
+                signature.args.len(),
+                signature.describe()
+            );
+            let error = if sig.inputs.is_empty() {
+                Error::new(sig.ident.span(), message)
+            } else {
+                Error::new_spanned(&sig.inputs, message)
+            };
+            push(&mut errors, error);
+        } else {
+            for (ty, expected_ty) in args.iter().zip(signature.args) {
+                if !same_type(&parse_type(expected_ty), ty) {
+                    push(&mut errors, Error::new_spanned(ty, format!("expected `{}`, as in `{}`", expected_ty, signature.describe())));
+                }
+            }
+        }
+
+        match &sig.output {
+            ReturnType::Type(_, ty) if same_type(&parse_type(signature.output), ty) => {}
+            ReturnType::Type(_, ty) => {
+                push(&mut errors, Error::new_spanned(ty, format!("expected `{}`, as in `{}`", signature.output, signature.describe())));
+            }
+            ReturnType::Default => {
+                push(&mut errors, Error::new(sig.ident.span(), format!("`{}` must return `{}`", name, signature.output)));
+            }
+        }
+    }
+
+    if let (Some(trait_name), Some(expected), Some(trait_path)) = (&trait_name, expected, trait_path) {
+        let missing: Vec<String> = expected
+            .iter()
+            .filter(|signature| {
+                !item.items.iter().any(|impl_item| matches!(impl_item, ImplItem::Fn(f) if f.sig.ident == signature.name))
+            })
+            .map(|signature| format!("`{}`", signature.describe()))
+            .collect();
+        if !missing.is_empty() {
+            push(&mut errors, Error::new_spanned(trait_path, format!("missing `{}` methods: {}", trait_name, missing.join(", "))));
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,61 @@
+//! Wasm import modules backed by [mochi_test::host].
+//!
+//! Buffers the module passes are copied out of its linear memory before
+//! calling into the host, and buffers the host fills are copied back in.
+
+use anyhow::{bail, Result};
+use wasmtime::{Extern, Linker, Memory};
+
+mod core;
+mod crypto;
+mod env;
+mod html;
+mod http;
+mod json;
+mod settings;
+mod storage;
+mod structs_meta;
+mod structs_video;
+
+type Caller<'a> = wasmtime::Caller<'a, ()>;
+
+/// Define every import a `mochi-rs` module may use.
+pub fn link(linker: &mut Linker<()>) -> Result<()> {
+    env::link(linker)?;
+    core::link(linker)?;
+    json::link(linker)?;
+    html::link(linker)?;
+    http::link(linker)?;
+    crypto::link(linker)?;
+    settings::link(linker)?;
+    storage::link(linker)?;
+    structs_meta::link(linker)?;
+    structs_video::link(linker)?;
+    Ok(())
+}
+
+fn memory(caller: &mut Caller<'_>) -> Result<Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => bail!("module does not export its memory"),
+    }
+}
+
+/// Copy `len` bytes at `ptr` out of the module's memory. A negative length,
+/// used for absent strings, reads nothing.
+fn read(caller: &mut Caller<'_>, ptr: i32, len: i32) -> Result<Vec<u8>> {
+    if len <= 0 {
+        return Ok(Vec::new());
+    }
+    let mut buf = vec![0; len as usize];
+    memory(caller)?.read(&*caller, ptr as u32 as usize, &mut buf)?;
+    Ok(buf)
+}
+
+/// Copy `data` into the module's memory at `ptr`.
+fn write(caller: &mut Caller<'_>, ptr: i32, data: &[u8]) -> Result<()> {
+    if !data.is_empty() {
+        memory(caller)?.write(&mut *caller, ptr as u32 as usize, data)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use mochi_test::host::core;
+use wasmtime::Linker;
+
+use super::{read, write, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("core", "copy", |ptr: i32| unsafe { core::copy(ptr) })?;
+    linker.func_wrap("core", "destroy", |ptr: i32| unsafe { core::destroy(ptr) })?;
+
+    linker.func_wrap("core", "create_array", || unsafe { core::create_array() })?;
+    linker.func_wrap("core", "create_obj", || unsafe { core::create_obj() })?;
+    linker.func_wrap("core", "create_null", || unsafe { core::create_null() })?;
+    linker.func_wrap("core", "create_string", |mut caller: Caller<'_>, ptr: i32, len: i32| -> Result<i32> {
+        let buf = read(&mut caller, ptr, len)?;
+        Ok(unsafe { core::create_string(buf.as_ptr(), len) })
+    })?;
+    linker.func_wrap("core", "create_bool", |value: i32| unsafe { core::create_bool(value != 0) })?;
+    linker.func_wrap("core", "create_float", |value: f64| unsafe { core::create_float(value) })?;
+    linker.func_wrap("core", "create_int", |value: i64| unsafe { core::create_int(value) })?;
+    linker.func_wrap("core", "create_error_with", |mut caller: Caller<'_>, kind: i32, ptr: i32, len: i32, details: i32| -> Result<i32> {
+        let buf = read(&mut caller, ptr, len)?;
+        Ok(unsafe { core::create_error_with(kind, buf.as_ptr(), len, details) })
+    })?;
+
+    linker.func_wrap("core", "log", |mut caller: Caller<'_>, level: i32, ptr: i32, len: i32| -> Result<()> {
+        let buf = read(&mut caller, ptr, len)?;
+        unsafe { core::log(level, buf.as_ptr(), buf.len()) };
+        Ok(())
+    })?;
+
+    linker.func_wrap("core", "ptr_kind", |ptr: i32| unsafe { core::ptr_kind(ptr) })?;
+
+    linker.func_wrap("core", "string_len", |ptr: i32| unsafe { core::string_len(ptr) })?;
+    linker.func_wrap("core", "read_string", |mut caller: Caller<'_>, ptr: i32, buf_ptr: i32, buf_len: i32| -> Result<()> {
+        let mut buf = vec![0; buf_len.max(0) as usize];
+        unsafe { core::read_string(ptr, buf.as_mut_ptr(), buf_len) };
+        write(&mut caller, buf_ptr, &buf)
+    })?;
+    linker.func_wrap("core", "read_int", |ptr: i32| unsafe { core::read_int(ptr) })?;
+    linker.func_wrap("core", "read_float", |ptr: i32| unsafe { core::read_float(ptr) })?;
+    linker.func_wrap("core", "read_bool", |ptr: i32| unsafe { core::read_bool(ptr) as i32 })?;
+
+    linker.func_wrap("core", "obj_len", |ptr: i32| unsafe { core::obj_len(ptr) as i32 })?;
+    linker.func_wrap("core", "obj_get", |mut caller: Caller<'_>, ptr: i32, key: i32, len: i32| -> Result<i32> {
+        let key = read(&mut caller, key, len)?;
+        Ok(unsafe { core::obj_get(ptr, key.as_ptr(), key.len()) })
+    })?;
+    linker.func_wrap("core", "obj_set", |mut caller: Caller<'_>, ptr: i32, key: i32, len: i32, value: i32| -> Result<()> {
+        let key = read(&mut caller, key, len)?;
+        unsafe { core::obj_set(ptr, key.as_ptr(), key.len(), value) };
+        Ok(())
+    })?;
+    linker.func_wrap("core", "obj_remove", |mut caller: Caller<'_>, ptr: i32, key: i32, len: i32| -> Result<()> {
+        let key = read(&mut caller, key, len)?;
+        unsafe { core::obj_remove(ptr, key.as_ptr(), key.len()) };
+        Ok(())
+    })?;
+    linker.func_wrap("core", "obj_keys", |ptr: i32| unsafe { core::obj_keys(ptr) })?;
+    linker.func_wrap("core", "obj_values", |ptr: i32| unsafe { core::obj_values(ptr) })?;
+
+    linker.func_wrap("core", "array_len", |ptr: i32| unsafe { core::array_len(ptr) })?;
+    linker.func_wrap("core", "array_get", |ptr: i32, idx: i32| unsafe { core::array_get(ptr, idx) })?;
+    linker.func_wrap("core", "array_set", |ptr: i32, idx: i32, value: i32| unsafe { core::array_set(ptr, idx, value) })?;
+    linker.func_wrap("core", "array_append", |ptr: i32, value: i32| unsafe { core::array_append(ptr, value) })?;
+    linker.func_wrap("core", "array_remove", |ptr: i32, idx: i32| unsafe { core::array_remove(ptr, idx) })?;
+    Ok(())
+}
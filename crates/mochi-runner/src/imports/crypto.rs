@@ -0,0 +1,85 @@
+use anyhow::Result;
+use mochi_test::host::crypto;
+use wasmtime::Linker;
+
+use super::{read, write, Caller};
+
+/// Imports taking a single buffer and returning a host pointer.
+macro_rules! with_buf {
+    ($linker:ident, $($name:ident),* $(,)?) => {
+        $($linker.func_wrap(
+            "crypto",
+            stringify!($name),
+            |mut caller: Caller<'_>, ptr: i32, len: i32| -> Result<i32> {
+                let buf = read(&mut caller, ptr, len)?;
+                Ok(unsafe { crypto::$name(buf.as_ptr(), len) })
+            },
+        )?;)*
+    };
+}
+
+/// Imports taking a message, key and iv, and returning a host pointer.
+macro_rules! aes {
+    ($linker:ident, $($name:ident),* $(,)?) => {
+        $($linker.func_wrap(
+            "crypto",
+            stringify!($name),
+            |mut caller: Caller<'_>, msg: i32, msg_len: i32, key: i32, key_len: i32, iv: i32, iv_len: i32| -> Result<i32> {
+                let msg = read(&mut caller, msg, msg_len)?;
+                let key = read(&mut caller, key, key_len)?;
+                let iv = read(&mut caller, iv, iv_len)?;
+                Ok(unsafe {
+                    crypto::$name(msg.as_ptr(), msg_len, key.as_ptr(), key_len, iv.as_ptr(), iv_len)
+                })
+            },
+        )?;)*
+    };
+}
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("crypto", "crypto_get_data_len", |ptr: i32| unsafe { crypto::crypto_get_data_len(ptr) })?;
+    linker.func_wrap(
+        "crypto",
+        "crypto_get_data",
+        |mut caller: Caller<'_>, ptr: i32, buf_ptr: i32, len: i32| -> Result<()> {
+            let mut buf = vec![0; len.max(0) as usize];
+            unsafe { crypto::crypto_get_data(ptr, buf.as_mut_ptr(), len) };
+            write(&mut caller, buf_ptr, &buf)
+        },
+    )?;
+
+    with_buf!(linker, crypto_base64_parse, crypto_base64_string, crypto_utf8_parse, crypto_md5_hash);
+    aes!(linker, crypto_aes_encrypt, crypto_aes_decrypt);
+
+    linker.func_wrap(
+        "crypto",
+        "crypto_pbkdf2",
+        |mut caller: Caller<'_>,
+         hash_algorithm: i32,
+         password: i32,
+         password_len: i32,
+         salt: i32,
+         salt_len: i32,
+         rounds: i32,
+         key_len: i32|
+         -> Result<i32> {
+            let password = read(&mut caller, password, password_len)?;
+            let salt = read(&mut caller, salt, salt_len)?;
+            Ok(unsafe {
+                crypto::crypto_pbkdf2(
+                    hash_algorithm,
+                    password.as_ptr(),
+                    password_len,
+                    salt.as_ptr(),
+                    salt_len,
+                    rounds,
+                    key_len,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap("crypto", "crypto_generate_random_bytes", |count: i32| unsafe {
+        crypto::crypto_generate_random_bytes(count)
+    })?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anyhow::{anyhow, Result};
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+/// Reads a string whose length is stored in the 4 bytes before it, as
+/// `abort` passes them.
+fn prefixed_string(caller: &mut Caller<'_>, ptr: i32) -> String {
+    let len = read(caller, ptr.wrapping_sub(4), 4)
+        .ok()
+        .and_then(|len| len.try_into().ok())
+        .map(i32::from_le_bytes)
+        .unwrap_or(0);
+    let bytes = read(caller, ptr, len).unwrap_or_default();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("env", "print", |mut caller: Caller<'_>, ptr: i32, len: i32| -> Result<()> {
+        let buf = read(&mut caller, ptr, len)?;
+        unsafe { mochi_test::host::core::print(buf.as_ptr(), buf.len()) };
+        Ok(())
+    })?;
+    linker.func_wrap("env", "sleep", |millis: i32| unsafe { mochi_test::host::core::sleep(millis) })?;
+    linker.func_wrap("env", "now", || unsafe { mochi_test::host::core::now() })?;
+    linker.func_wrap(
+        "env",
+        "abort",
+        |mut caller: Caller<'_>, message: i32, file: i32, line: i32, column: i32| -> Result<()> {
+            let message = prefixed_string(&mut caller, message);
+            let file = prefixed_string(&mut caller, file);
+            Err(anyhow!("module panicked at {}:{}:{}: {}", file, line, column, message))
+        },
+    )?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use mochi_test::host::html;
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+/// Imports taking a node pointer and returning an `i32`.
+macro_rules! node {
+    ($linker:ident, $($name:literal => $f:ident),* $(,)?) => {
+        $($linker.func_wrap("html", $name, |ptr: i32| unsafe { html::$f(ptr) as i32 })?;)*
+    };
+}
+
+/// Imports taking a node pointer and a string, and returning an `i32`.
+macro_rules! node_with_str {
+    ($linker:ident, $($name:literal => $f:ident),* $(,)?) => {
+        $($linker.func_wrap(
+            "html",
+            $name,
+            |mut caller: Caller<'_>, ptr: i32, str_ptr: i32, str_len: i32| -> Result<i32> {
+                let buf = read(&mut caller, str_ptr, str_len)?;
+                Ok(unsafe { html::$f(ptr, buf.as_ptr(), buf.len()) as i32 })
+            },
+        )?;)*
+    };
+}
+
+/// Imports parsing a document, optionally with a base uri.
+macro_rules! parse {
+    ($linker:ident, $name:literal => $f:ident, $name_with_uri:literal => $f_with_uri:ident) => {
+        $linker.func_wrap("html", $name, |mut caller: Caller<'_>, ptr: i32, len: i32| -> Result<i32> {
+            let buf = read(&mut caller, ptr, len)?;
+            Ok(unsafe { html::$f(buf.as_ptr(), buf.len()) })
+        })?;
+        $linker.func_wrap(
+            "html",
+            $name_with_uri,
+            |mut caller: Caller<'_>, ptr: i32, len: i32, uri_ptr: i32, uri_len: i32| -> Result<i32> {
+                let buf = read(&mut caller, ptr, len)?;
+                let uri = read(&mut caller, uri_ptr, uri_len)?;
+                Ok(unsafe { html::$f_with_uri(buf.as_ptr(), buf.len(), uri.as_ptr(), uri.len()) })
+            },
+        )?;
+    };
+}
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    parse!(linker, "parse" => scraper_parse, "parse_with_uri" => scraper_parse_with_uri);
+    parse!(
+        linker,
+        "parse_fragment" => scraper_parse_fragment,
+        "parse_fragment_with_uri" => scraper_parse_fragment_with_uri
+    );
+
+    node_with_str!(
+        linker,
+        "select" => scraper_select,
+        "select_first" => scraper_select_first,
+        "closest" => scraper_closest,
+        "attr" => scraper_attr,
+        "set_text" => scraper_set_text,
+        "set_html" => scraper_set_html,
+        "prepend" => scraper_prepend,
+        "append" => scraper_append,
+        "has_class" => scraper_has_class,
+        "has_attr" => scraper_has_attr,
+    );
+
+    node!(
+        linker,
+        "first" => scraper_first,
+        "last" => scraper_last,
+        "next" => scraper_next,
+        "previous" => scraper_previous,
+        "parent" => scraper_parent,
+        "children" => scraper_children,
+        "siblings" => scraper_siblings,
+        "index_in_parent" => scraper_index_in_parent,
+        "base_uri" => scraper_base_uri,
+        "body" => scraper_body,
+        "text" => scraper_text,
+        "untrimmed_text" => scraper_untrimmed_text,
+        "own_text" => scraper_own_text,
+        "data" => scraper_data,
+        "array" => scraper_array,
+        "html" => scraper_html,
+        "outer_html" => scraper_outer_html,
+        "escape" => scraper_escape,
+        "unescape" => scraper_unescape,
+        "id" => scraper_id,
+        "tag_name" => scraper_tag_name,
+        "class_name" => scraper_class_name,
+        "attributes" => scraper_attributes,
+    );
+    Ok(())
+}
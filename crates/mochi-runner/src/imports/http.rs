@@ -0,0 +1,71 @@
+use anyhow::Result;
+use mochi_test::host::http;
+use wasmtime::Linker;
+
+use super::{read, write, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("http", "create", |method: i32| unsafe { http::request_create(method) })?;
+    linker.func_wrap("http", "send", |ptr: i32| unsafe { http::request_send(ptr) })?;
+    linker.func_wrap("http", "send_all", |mut caller: Caller<'_>, ptrs: i32, len: i32| -> Result<()> {
+        let bytes = read(&mut caller, ptrs, len.max(0).saturating_mul(4))?;
+        let ptrs: Vec<i32> = bytes
+            .chunks_exact(4)
+            .map(|ptr| i32::from_le_bytes([ptr[0], ptr[1], ptr[2], ptr[3]]))
+            .collect();
+        unsafe { http::request_send_all(ptrs.as_ptr(), ptrs.len() as i32) };
+        Ok(())
+    })?;
+    linker.func_wrap("http", "reset", |ptr: i32| unsafe { http::request_reset(ptr) })?;
+    linker.func_wrap("http", "close", |ptr: i32| unsafe { http::request_close(ptr) })?;
+
+    linker.func_wrap("http", "set_url", |mut caller: Caller<'_>, ptr: i32, url: i32, len: i32| -> Result<()> {
+        let url = read(&mut caller, url, len)?;
+        unsafe { http::request_set_url(ptr, url.as_ptr(), len) };
+        Ok(())
+    })?;
+    linker.func_wrap(
+        "http",
+        "set_header",
+        |mut caller: Caller<'_>, ptr: i32, key: i32, key_len: i32, value: i32, value_len: i32| -> Result<()> {
+            let key = read(&mut caller, key, key_len)?;
+            let value = read(&mut caller, value, value_len)?;
+            unsafe { http::request_set_header(ptr, key.as_ptr(), key_len, value.as_ptr(), value_len) };
+            Ok(())
+        },
+    )?;
+    linker.func_wrap("http", "set_body", |mut caller: Caller<'_>, ptr: i32, data: i32, len: i32| -> Result<()> {
+        let data = read(&mut caller, data, len)?;
+        unsafe { http::request_set_body(ptr, data.as_ptr(), len) };
+        Ok(())
+    })?;
+    linker.func_wrap("http", "set_method", |ptr: i32, method: i32| unsafe { http::request_set_method(ptr, method) })?;
+
+    linker.func_wrap("http", "get_method", |ptr: i32| unsafe { http::request_get_method(ptr) })?;
+    linker.func_wrap("http", "get_url", |ptr: i32| unsafe { http::request_get_url(ptr) })?;
+    linker.func_wrap("http", "get_header", |mut caller: Caller<'_>, ptr: i32, key: i32, len: i32| -> Result<i32> {
+        let key = read(&mut caller, key, len)?;
+        Ok(unsafe { http::request_get_header(ptr, key.as_ptr(), len) })
+    })?;
+    linker.func_wrap("http", "get_status_code", |ptr: i32| unsafe { http::request_get_status_code(ptr) })?;
+    linker.func_wrap("http", "get_response_url", |ptr: i32| unsafe { http::request_get_response_url(ptr) })?;
+    linker.func_wrap("http", "get_response_headers", |ptr: i32| unsafe { http::request_get_response_headers(ptr) })?;
+    linker.func_wrap("http", "get_data_len", |ptr: i32| unsafe { http::request_get_data_len(ptr) })?;
+    linker.func_wrap("http", "get_data", |mut caller: Caller<'_>, ptr: i32, buf_ptr: i32, len: i32| -> Result<()> {
+        let mut buf = vec![0; len.max(0) as usize];
+        unsafe { http::request_get_data(ptr, buf.as_mut_ptr(), len) };
+        write(&mut caller, buf_ptr, &buf)
+    })?;
+    linker.func_wrap("http", "get_cache", |mut caller: Caller<'_>, key: i32, len: i32| -> Result<i32> {
+        let key = read(&mut caller, key, len)?;
+        Ok(unsafe { http::get_cache(key.as_ptr(), len) })
+    })?;
+    linker.func_wrap("http", "set_cache", |mut caller: Caller<'_>, key: i32, len: i32, ptr: i32| -> Result<()> {
+        let key = read(&mut caller, key, len)?;
+        unsafe { http::set_cache(key.as_ptr(), len, ptr) };
+        Ok(())
+    })?;
+    linker.func_wrap("http", "get_cookies", || unsafe { http::get_cookies() })?;
+    linker.func_wrap("http", "set_cookies", |ptr: i32| unsafe { http::set_cookies(ptr) })?;
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use mochi_test::host::json;
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("json", "json_parse", |mut caller: Caller<'_>, ptr: i32, len: i32| -> Result<i32> {
+        let buf = read(&mut caller, ptr, len)?;
+        Ok(unsafe { json::json_parse(buf.as_ptr(), len) })
+    })?;
+    linker.func_wrap("json", "json_stringify", |ptr: i32, pretty: i32| unsafe {
+        json::json_stringify(ptr, pretty != 0)
+    })?;
+    Ok(())
+}
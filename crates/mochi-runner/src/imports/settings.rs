@@ -0,0 +1,13 @@
+use anyhow::Result;
+use mochi_test::host::settings;
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("settings", "get", |mut caller: Caller<'_>, id: i32, len: i32| -> Result<i32> {
+        let id = read(&mut caller, id, len)?;
+        Ok(unsafe { settings::get(id.as_ptr(), len) })
+    })?;
+    Ok(())
+}
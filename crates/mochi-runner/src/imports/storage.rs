@@ -0,0 +1,48 @@
+use anyhow::Result;
+use mochi_test::host::storage;
+use wasmtime::Linker;
+
+use super::{read, write, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap("storage", "get", |mut caller: Caller<'_>, key: i32, len: i32| -> Result<i32> {
+        let key = read(&mut caller, key, len)?;
+        Ok(unsafe { storage::get(key.as_ptr(), len) })
+    })?;
+    linker.func_wrap("storage", "set", |mut caller: Caller<'_>, key: i32, len: i32, ptr: i32| -> Result<()> {
+        let key = read(&mut caller, key, len)?;
+        unsafe { storage::set(key.as_ptr(), len, ptr) };
+        Ok(())
+    })?;
+    linker.func_wrap("storage", "get_data_len", |mut caller: Caller<'_>, key: i32, len: i32| -> Result<i32> {
+        let key = read(&mut caller, key, len)?;
+        Ok(unsafe { storage::get_data_len(key.as_ptr(), len) })
+    })?;
+    linker.func_wrap(
+        "storage",
+        "get_data",
+        |mut caller: Caller<'_>, key: i32, len: i32, buf_ptr: i32, buf_len: i32| -> Result<()> {
+            let key = read(&mut caller, key, len)?;
+            let mut buf = vec![0; buf_len.max(0) as usize];
+            unsafe { storage::get_data(key.as_ptr(), len, buf.as_mut_ptr(), buf_len) };
+            write(&mut caller, buf_ptr, &buf)
+        },
+    )?;
+    linker.func_wrap(
+        "storage",
+        "set_data",
+        |mut caller: Caller<'_>, key: i32, len: i32, data: i32, data_len: i32| -> Result<()> {
+            let key = read(&mut caller, key, len)?;
+            let data = read(&mut caller, data, data_len)?;
+            unsafe { storage::set_data(key.as_ptr(), len, data.as_ptr(), data_len) };
+            Ok(())
+        },
+    )?;
+    linker.func_wrap("storage", "remove", |mut caller: Caller<'_>, key: i32, len: i32| -> Result<()> {
+        let key = read(&mut caller, key, len)?;
+        unsafe { storage::remove(key.as_ptr(), len) };
+        Ok(())
+    })?;
+    linker.func_wrap("storage", "keys", || unsafe { storage::keys() })?;
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use anyhow::Result;
+use mochi_test::host::structs_meta as meta;
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap(
+        "structs_meta",
+        "create_search_filter_option",
+        |mut caller: Caller<'_>, id: i32, id_len: i32, name: i32, name_len: i32| -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let name = read(&mut caller, name, name_len)?;
+            Ok(unsafe { meta::create_search_filter_option(id.as_ptr(), id_len, name.as_ptr(), name_len) })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_search_filter",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         name: i32,
+         name_len: i32,
+         options: i32,
+         multiselect: i32,
+         required: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let name = read(&mut caller, name, name_len)?;
+            Ok(unsafe {
+                meta::create_search_filter(
+                    id.as_ptr(),
+                    id_len,
+                    name.as_ptr(),
+                    name_len,
+                    options,
+                    multiselect != 0,
+                    required != 0,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_paging",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         previous_page: i32,
+         previous_page_len: i32,
+         next_page: i32,
+         next_page_len: i32,
+         items: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let previous_page = read(&mut caller, previous_page, previous_page_len)?;
+            let next_page = read(&mut caller, next_page, next_page_len)?;
+            Ok(unsafe {
+                meta::create_paging(
+                    id.as_ptr(),
+                    id_len,
+                    previous_page.as_ptr(),
+                    previous_page_len,
+                    next_page.as_ptr(),
+                    next_page_len,
+                    items,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_discover_listing",
+        |mut caller: Caller<'_>, title: i32, title_len: i32, listing_type: i32, paging: i32| -> Result<i32> {
+            let title = read(&mut caller, title, title_len)?;
+            Ok(unsafe { meta::create_discover_listing(title.as_ptr(), title_len, listing_type, paging) })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         title: i32,
+         title_len: i32,
+         poster_image: i32,
+         poster_image_len: i32,
+         banner_image: i32,
+         banner_image_len: i32,
+         playlist_type: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let title = read(&mut caller, title, title_len)?;
+            let poster_image = read(&mut caller, poster_image, poster_image_len)?;
+            let banner_image = read(&mut caller, banner_image, banner_image_len)?;
+            Ok(unsafe {
+                meta::create_playlist(
+                    id.as_ptr(),
+                    id_len,
+                    title.as_ptr(),
+                    title_len,
+                    poster_image.as_ptr(),
+                    poster_image_len,
+                    banner_image.as_ptr(),
+                    banner_image_len,
+                    playlist_type,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_details",
+        |mut caller: Caller<'_>,
+         description: i32,
+         description_len: i32,
+         alternative_titles: i32,
+         alternative_posters: i32,
+         alternative_banners: i32,
+         genres: i32,
+         year_released: i32,
+         ratings: i32,
+         previews: i32|
+         -> Result<i32> {
+            let description = read(&mut caller, description, description_len)?;
+            Ok(unsafe {
+                meta::create_playlist_details(
+                    description.as_ptr(),
+                    description_len,
+                    alternative_titles,
+                    alternative_posters,
+                    alternative_banners,
+                    genres,
+                    year_released,
+                    ratings,
+                    previews,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_preview",
+        |mut caller: Caller<'_>,
+         title: i32,
+         title_len: i32,
+         description: i32,
+         description_len: i32,
+         thumbnail: i32,
+         thumbnail_len: i32,
+         link: i32,
+         link_len: i32,
+         preview_type: i32|
+         -> Result<i32> {
+            let title = read(&mut caller, title, title_len)?;
+            let description = read(&mut caller, description, description_len)?;
+            let thumbnail = read(&mut caller, thumbnail, thumbnail_len)?;
+            let link = read(&mut caller, link, link_len)?;
+            Ok(unsafe {
+                meta::create_playlist_preview(
+                    title.as_ptr(),
+                    title_len,
+                    description.as_ptr(),
+                    description_len,
+                    thumbnail.as_ptr(),
+                    thumbnail_len,
+                    link.as_ptr(),
+                    link_len,
+                    preview_type,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_item",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         title: i32,
+         title_len: i32,
+         description: i32,
+         description_len: i32,
+         thumbnail: i32,
+         thumbnail_len: i32,
+         number: f64,
+         timestamp: i32,
+         timestamp_len: i32,
+         tags: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let title = read(&mut caller, title, title_len)?;
+            let description = read(&mut caller, description, description_len)?;
+            let thumbnail = read(&mut caller, thumbnail, thumbnail_len)?;
+            let timestamp = read(&mut caller, timestamp, timestamp_len)?;
+            Ok(unsafe {
+                meta::create_playlist_item(
+                    id.as_ptr(),
+                    id_len,
+                    title.as_ptr(),
+                    title_len,
+                    description.as_ptr(),
+                    description_len,
+                    thumbnail.as_ptr(),
+                    thumbnail_len,
+                    number,
+                    timestamp.as_ptr(),
+                    timestamp_len,
+                    tags,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap("structs_meta", "create_playlist_items_response", |contents: i32, all_groups: i32| unsafe {
+        meta::create_playlist_items_response(contents, all_groups)
+    })?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_group",
+        |mut caller: Caller<'_>, id: f64, display_title: i32, display_title_len: i32| -> Result<i32> {
+            let display_title = read(&mut caller, display_title, display_title_len)?;
+            Ok(unsafe { meta::create_playlist_group(id, display_title.as_ptr(), display_title_len) })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_group_page",
+        |mut caller: Caller<'_>, id: i32, id_len: i32, display_name: i32, display_name_len: i32| -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let display_name = read(&mut caller, display_name, display_name_len)?;
+            Ok(unsafe {
+                meta::create_playlist_group_page(id.as_ptr(), id_len, display_name.as_ptr(), display_name_len)
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_meta",
+        "create_playlist_group_items",
+        |group_id: f64, pagings: i32, all_pages: i32| unsafe {
+            meta::create_playlist_group_items(group_id, pagings, all_pages)
+        },
+    )?;
+    Ok(())
+}
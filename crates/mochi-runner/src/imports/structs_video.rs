@@ -0,0 +1,112 @@
+use anyhow::Result;
+use mochi_test::host::structs_video as video;
+use wasmtime::Linker;
+
+use super::{read, Caller};
+
+pub(super) fn link(linker: &mut Linker<()>) -> Result<()> {
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_source",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         display_name: i32,
+         display_name_len: i32,
+         description: i32,
+         description_len: i32,
+         servers: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let display_name = read(&mut caller, display_name, display_name_len)?;
+            let description = read(&mut caller, description, description_len)?;
+            Ok(unsafe {
+                video::create_episode_source(
+                    id.as_ptr(),
+                    id_len,
+                    display_name.as_ptr(),
+                    display_name_len,
+                    description.as_ptr(),
+                    description_len,
+                    servers,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_server",
+        |mut caller: Caller<'_>,
+         id: i32,
+         id_len: i32,
+         display_name: i32,
+         display_name_len: i32,
+         description: i32,
+         description_len: i32|
+         -> Result<i32> {
+            let id = read(&mut caller, id, id_len)?;
+            let display_name = read(&mut caller, display_name, display_name_len)?;
+            let description = read(&mut caller, description, description_len)?;
+            Ok(unsafe {
+                video::create_episode_server(
+                    id.as_ptr(),
+                    id_len,
+                    display_name.as_ptr(),
+                    display_name_len,
+                    description.as_ptr(),
+                    description_len,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_server_response",
+        |links: i32, subtitles: i32, skip_times: i32, headers: i32| unsafe {
+            video::create_episode_server_response(links, subtitles, skip_times, headers)
+        },
+    )?;
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_server_link",
+        |mut caller: Caller<'_>, url: i32, url_len: i32, quality: i32, format: i32| -> Result<i32> {
+            let url = read(&mut caller, url, url_len)?;
+            Ok(unsafe { video::create_episode_server_link(url.as_ptr(), url_len, quality, format) })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_server_subtitle",
+        |mut caller: Caller<'_>,
+         url: i32,
+         url_len: i32,
+         name: i32,
+         name_len: i32,
+         format: i32,
+         default: i32,
+         autoselect: i32|
+         -> Result<i32> {
+            let url = read(&mut caller, url, url_len)?;
+            let name = read(&mut caller, name, name_len)?;
+            Ok(unsafe {
+                video::create_episode_server_subtitle(
+                    url.as_ptr(),
+                    url_len,
+                    name.as_ptr(),
+                    name_len,
+                    format,
+                    default != 0,
+                    autoselect != 0,
+                )
+            })
+        },
+    )?;
+    linker.func_wrap(
+        "structs_video",
+        "create_episode_server_skip_time",
+        |start_time: f32, end_time: f32, skip_type: i32| unsafe {
+            video::create_episode_server_skip_time(start_time, end_time, skip_type)
+        },
+    )?;
+    Ok(())
+}
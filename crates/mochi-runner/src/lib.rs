@@ -0,0 +1,242 @@
+//! Loads a module built with `mochi-rs` into wasmtime and calls the exports
+//! generated by `#[mochi_bind]`.
+//!
+//! The host imports are served by `mochi-test`, so linking fails if a module
+//! declares an import the host doesn't provide, or with a different signature.
+
+mod imports;
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use mochi_test::host::{self, Value};
+use wasmtime::{Engine, Instance, Linker, Module, Store, Val, ValType};
+
+pub use imports::link;
+
+pub struct Runner {
+    store: Store<()>,
+    instance: Instance,
+}
+
+impl Runner {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let engine = Engine::default();
+        let module = Module::from_file(&engine, path)?;
+        Self::with_module(&engine, &module)
+    }
+
+    pub fn with_module(engine: &Engine, module: &Module) -> Result<Self> {
+        let mut linker = Linker::new(engine);
+        link(&mut linker)?;
+        let mut store = Store::new(engine, ());
+        let instance = linker
+            .instantiate(&mut store, module)
+            .context("module imports do not match the host")?;
+        Ok(Self { store, instance })
+    }
+
+    /// Names of the functions exported by the module.
+    pub fn exports(&mut self) -> Vec<String> {
+        self.instance
+            .exports(&mut self.store)
+            .filter(|export| export.clone().into_func().is_some())
+            .map(|export| export.name().to_owned())
+            .collect()
+    }
+
+    /// Call an export, passing each argument the way `#[mochi_bind]` reads
+    /// it, and read back the host pointer it returns.
+    ///
+    /// The arguments have to add up to the export's parameters, e.g. a
+    /// [Arg::Str] is a `ptr: i32, len: i32` pair.
+    pub fn call(&mut self, name: &str, args: Vec<Arg>) -> Result<Value> {
+        let func = self
+            .instance
+            .get_func(&mut self.store, name)
+            .ok_or_else(|| anyhow!("module does not export `{}`", name))?;
+        let ty = func.ty(&self.store);
+        let types: Vec<ValType> = ty.params().collect();
+
+        // Check every argument before writing strings into the module.
+        let mut expected = Vec::with_capacity(types.len());
+        for arg in &args {
+            arg.wasm_types(&types, &mut expected)?;
+        }
+        let matches = expected.len() == types.len()
+            && expected.iter().zip(&types).all(|(expected, ty)| ValType::eq(expected, ty));
+        if !matches {
+            bail!(
+                "`{}` takes ({}), but the arguments are passed as ({})",
+                name,
+                join(&types),
+                join(&expected)
+            );
+        }
+
+        let mut params = Vec::with_capacity(types.len());
+        for arg in args {
+            self.lower(arg, &types, &mut params)?;
+        }
+        let mut results = vec![Val::I32(0); ty.results().len()];
+        func.call(&mut self.store, &params, &mut results)?;
+
+        match results.first() {
+            None => Ok(Value::Null),
+            Some(Val::I32(ptr)) => Ok(host::take(*ptr).unwrap_or(Value::Null)),
+            Some(other) => bail!("`{}` returned {:?} instead of a host pointer", name, other),
+        }
+    }
+
+    fn lower(&mut self, arg: Arg, types: &[ValType], params: &mut Vec<Val>) -> Result<()> {
+        match arg {
+            Arg::Ptr(value) => params.push(Val::I32(host::insert(value))),
+            Arg::NullPtr => params.push(Val::I32(-1)),
+            Arg::Str(Some(string)) => {
+                let ptr = self.alloc(string.as_bytes())?;
+                params.extend([Val::I32(ptr), Val::I32(string.len() as i32)]);
+            }
+            Arg::Str(None) => params.extend([Val::I32(0), Val::I32(-1)]),
+            Arg::I32(value) => params.push(Val::I32(value)),
+            Arg::I64(value) => params.push(Val::I64(value)),
+            Arg::F32(value) => params.push(Val::F32(value.to_bits())),
+            Arg::F64(value) => params.push(Val::F64(value.to_bits())),
+            Arg::Bool(value) => params.push(Val::I32(value as i32)),
+            Arg::Option(Some(value)) => {
+                params.push(Val::I32(1));
+                self.lower(*value, types, params)?;
+            }
+            Arg::Option(None) => {
+                params.push(Val::I32(0));
+                params.push(match &types[params.len()] {
+                    ValType::I64 => Val::I64(0),
+                    ValType::F32 => Val::F32(0),
+                    ValType::F64 => Val::F64(0),
+                    _ => Val::I32(0),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy `data` into a buffer from the module's `mochi_alloc`, which the
+    /// export it's passed to takes ownership of.
+    fn alloc(&mut self, data: &[u8]) -> Result<i32> {
+        let alloc = self
+            .instance
+            .get_typed_func::<i32, i32>(&mut self.store, "mochi_alloc")
+            .context("module does not export `mochi_alloc`")?;
+        let memory = self
+            .instance
+            .get_memory(&mut self.store, "memory")
+            .ok_or_else(|| anyhow!("module does not export its memory"))?;
+        let ptr = alloc.call(&mut self.store, data.len() as i32)?;
+        memory.write(&mut self.store, ptr as u32 as usize, data)?;
+        Ok(ptr)
+    }
+}
+
+/// An argument to an export, following the table in `mochi_bind`'s docs.
+#[derive(Debug, Clone)]
+pub enum Arg {
+    /// Any other `T`, inserted into the host and passed as an `i32` host
+    /// pointer. The traits the host calls take only these.
+    Ptr(Value),
+    /// `None` of any other `Option<T>`, passed as a null host pointer.
+    NullPtr,
+    /// `&str` or `String`, or `Option` of them, passed as `ptr: i32, len: i32`
+    /// with a negative `len` for `None`.
+    Str(Option<String>),
+    /// `i32` and the smaller integers.
+    I32(i32),
+    /// `i64` or `u64`.
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    /// Passed as an `i32`.
+    Bool(bool),
+    /// `Option` of a number or `bool`, passed as `some: i32` and then the
+    /// value, which is zero for `None`.
+    Option(Option<Box<Arg>>),
+}
+
+impl Arg {
+    /// Push the wasm types the argument is passed as. `None` of a number
+    /// takes whichever number type the export has in its place.
+    fn wasm_types(&self, types: &[ValType], expected: &mut Vec<ValType>) -> Result<()> {
+        match self {
+            Arg::Ptr(_) | Arg::NullPtr | Arg::I32(_) | Arg::Bool(_) => expected.push(ValType::I32),
+            Arg::Str(_) => expected.extend([ValType::I32, ValType::I32]),
+            Arg::I64(_) => expected.push(ValType::I64),
+            Arg::F32(_) => expected.push(ValType::F32),
+            Arg::F64(_) => expected.push(ValType::F64),
+            Arg::Option(Some(value)) => match **value {
+                Arg::I32(_) | Arg::I64(_) | Arg::F32(_) | Arg::F64(_) | Arg::Bool(_) => {
+                    expected.push(ValType::I32);
+                    value.wasm_types(types, expected)?;
+                }
+                _ => bail!("only numbers and `bool` are passed as `Arg::Option`, not {:?}", value),
+            },
+            Arg::Option(None) => {
+                expected.push(ValType::I32);
+                expected.push(match types.get(expected.len()) {
+                    Some(ty @ (ValType::I64 | ValType::F32 | ValType::F64)) => ty.clone(),
+                    _ => ValType::I32,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Value> for Arg {
+    fn from(value: Value) -> Self {
+        Arg::Ptr(value)
+    }
+}
+
+impl From<&str> for Arg {
+    fn from(value: &str) -> Self {
+        Arg::Str(Some(value.to_owned()))
+    }
+}
+
+impl From<String> for Arg {
+    fn from(value: String) -> Self {
+        Arg::Str(Some(value))
+    }
+}
+
+impl From<i32> for Arg {
+    fn from(value: i32) -> Self {
+        Arg::I32(value)
+    }
+}
+
+impl From<i64> for Arg {
+    fn from(value: i64) -> Self {
+        Arg::I64(value)
+    }
+}
+
+impl From<f32> for Arg {
+    fn from(value: f32) -> Self {
+        Arg::F32(value)
+    }
+}
+
+impl From<f64> for Arg {
+    fn from(value: f64) -> Self {
+        Arg::F64(value)
+    }
+}
+
+impl From<bool> for Arg {
+    fn from(value: bool) -> Self {
+        Arg::Bool(value)
+    }
+}
+
+fn join(types: &[ValType]) -> String {
+    types.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,53 @@
+use std::process::ExitCode;
+
+use mochi_runner::{Arg, Runner};
+use mochi_test::host::Value;
+
+const USAGE: &str = "usage: mochi-runner <module.wasm> [export] [args...]
+
+Without an export, lists the module's exports. Arguments are host values
+parsed as JSON, falling back to a plain string, e.g.:
+
+    mochi-runner source.wasm search '{\"query\": \"naruto\", \"filters\": []}'
+    mochi-runner source.wasm playlist_details one-piece";
+
+fn argument(arg: &str) -> Arg {
+    let value = serde_json::from_str::<serde_json::Value>(arg)
+        .map(Value::from)
+        .unwrap_or_else(|_| Value::from(arg));
+    Arg::Ptr(value)
+}
+
+fn run(args: &[String]) -> anyhow::Result<()> {
+    let mut runner = Runner::new(&args[0])?;
+
+    match args.get(1) {
+        Some(export) => {
+            let args = args[2..].iter().map(|arg| argument(arg)).collect();
+            let value = runner.call(export, args)?;
+            println!("{}", serde_json::to_string_pretty(&value.to_json())?);
+        }
+        None => {
+            for export in runner.exports() {
+                println!("{}", export);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args[0] == "-h" || args[0] == "--help" {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {:#}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! A source built to wasm by the runner tests, so linking it checks that the
+//! runner provides the imports `mochi-rs` declares, with the same signatures.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use mochi::error::{Context, MochiError, Result};
+use mochi::std::html::Node;
+use mochi::std::storage::Storage;
+use mochi::structs::meta::*;
+use mochi::{mochi_bind, mochi_source};
+
+const PAGE: &str = r#"
+<ul class="results">
+    <li><a href="/watch/naruto" title="Naruto"><img src="/naruto.jpg"></a></li>
+    <li><a href="/watch/naruto-shippuden" title="Naruto Shippuden"><img src="/shippuden.jpg"></a></li>
+</ul>
+"#;
+
+#[mochi_source(name = "Fixture", base_url = "https://fixture.to")]
+pub struct Fixture;
+
+#[mochi_bind]
+impl Meta for Fixture {
+    fn search_filters() -> SearchFilters {
+        SearchFilters { filters: Vec::new() }
+    }
+
+    fn search(search_query: SearchQuery) -> Result<Paging<Playlist>> {
+        Storage::new().set_string("last_query", &search_query.query);
+        let document = Node::new_with_uri(PAGE, "https://fixture.to/search")?;
+        let items = document
+            .select(".results a")
+            .map(|link| Playlist {
+                id: link.attr("href"),
+                title: Some(link.attr("title")),
+                poster_image: Some(link.select("img").attr("abs:src")),
+                banner_image: None,
+                url: link.attr("abs:href"),
+                status: PlaylistStatus::Unknown,
+                playlist_type: PlaylistType::Video,
+            })
+            .collect();
+        Ok(Paging {
+            id: search_query.page.unwrap_or_else(|| String::from("1")),
+            previous_page: None,
+            next_page: None,
+            items,
+        })
+    }
+
+    fn discover_listings() -> Result<DiscoverListings> {
+        Ok(DiscoverListings(Vec::new()))
+    }
+
+    fn playlist_details(_id: String) -> Result<PlaylistDetails> {
+        Err(MochiError::Unimplemented)
+    }
+}
+
+/// Exports taking each kind of argument `#[mochi_bind]` passes by value,
+/// which describe what they received.
+#[mochi_bind]
+impl Fixture {
+    fn echo_str(borrowed: &str, owned: String) -> String {
+        format!("{}|{}", borrowed, owned)
+    }
+
+    fn echo_option_str(borrowed: Option<&str>, owned: Option<String>) -> String {
+        format!("{:?}|{:?}", borrowed, owned)
+    }
+
+    fn echo_i32(a: i32, b: u8, c: usize) -> String {
+        format!("{}|{}|{}", a, b, c)
+    }
+
+    fn echo_i64(a: i64, b: u64) -> String {
+        format!("{}|{}", a, b)
+    }
+
+    fn echo_float(a: f32, b: f64) -> String {
+        format!("{}|{}", a, b)
+    }
+
+    fn echo_bool(a: bool, b: bool) -> String {
+        format!("{}|{}", a, b)
+    }
+
+    fn echo_option(a: Option<i32>, b: Option<i64>, c: Option<f64>, d: Option<bool>) -> String {
+        format!("{:?}|{:?}|{:?}|{:?}", a, b, c, d)
+    }
+
+    fn echo_ptr(query: SearchQuery) -> String {
+        format!("{}|{:?}", query.query, query.page)
+    }
+
+    fn echo_option_ptr(query: Option<SearchQuery>) -> String {
+        format!("{:?}", query.map(|query| query.query))
+    }
+
+    fn fail(status: i32, url: &str) -> Result<String> {
+        Err(MochiError::Http { status, url: url.into() }).context("while failing on purpose")
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use mochi_runner::{Arg, Runner};
+use mochi_test::host::Value;
+use serde_json::json;
+
+/// Builds `tests/fixture` to wasm once for all the tests.
+fn fixture() -> &'static PathBuf {
+    static FIXTURE: OnceLock<PathBuf> = OnceLock::new();
+    FIXTURE.get_or_init(|| {
+        let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("fixture");
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+        let status = Command::new(cargo)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(["build", "--quiet", "-p", "mochi-runner-fixture", "--target", "wasm32-unknown-unknown"])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .expect("failed to run cargo");
+        assert!(status.success(), "failed to build the fixture, is the wasm32-unknown-unknown target installed?");
+        target_dir.join("wasm32-unknown-unknown/debug/mochi_runner_fixture.wasm")
+    })
+}
+
+#[test]
+fn links_and_calls_an_export() {
+    let mut runner = Runner::new(fixture()).expect("the fixture's imports should link");
+    let exports = runner.exports();
+    for export in ["source_info", "search", "search_filters", "discover_listings", "playlist_details"] {
+        assert!(exports.iter().any(|name| name == export), "missing export `{}`", export);
+    }
+
+    let paging = runner
+        .call("search", vec![Value::from(json!({ "query": "naruto", "filters": [] })).into()])
+        .unwrap();
+    let items = paging.get("items").and_then(Value::as_array).unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].get("title").and_then(Value::as_str), Some("Naruto Shippuden"));
+    assert_eq!(items[1].get("posterImage").and_then(Value::as_str), Some("https://fixture.to/shippuden.jpg"));
+}
+
+/// Calls an export of the fixture and returns the string it describes its
+/// arguments with.
+fn echo(export: &str, args: Vec<Arg>) -> String {
+    let mut runner = Runner::new(fixture()).unwrap();
+    let value = runner.call(export, args).unwrap();
+    value.as_str().unwrap().to_owned()
+}
+
+#[test]
+fn passes_strings_through_mochi_alloc() {
+    assert_eq!(echo("echo_str", vec!["naruto".into(), "日本 ✓".into()]), "naruto|日本 ✓");
+    assert_eq!(echo("echo_str", vec!["".into(), String::from("b").into()]), "|b");
+}
+
+#[test]
+fn passes_absent_strings_as_negative_lengths() {
+    assert_eq!(
+        echo("echo_option_str", vec![Arg::Str(None), "page".into()]),
+        r#"None|Some("page")"#
+    );
+    assert_eq!(
+        echo("echo_option_str", vec![Arg::Str(Some(String::new())), Arg::Str(None)]),
+        r#"Some("")|None"#
+    );
+}
+
+#[test]
+fn passes_small_integers_as_i32() {
+    assert_eq!(echo("echo_i32", vec![(-7).into(), 255.into(), 42.into()]), "-7|255|42");
+}
+
+#[test]
+fn passes_64_bit_integers_as_i64() {
+    assert_eq!(echo("echo_i64", vec![i64::MIN.into(), (-1i64).into()]), format!("{}|{}", i64::MIN, u64::MAX));
+}
+
+#[test]
+fn passes_floats() {
+    assert_eq!(echo("echo_float", vec![1.5f32.into(), (-0.25f64).into()]), "1.5|-0.25");
+}
+
+#[test]
+fn passes_bools_as_i32() {
+    assert_eq!(echo("echo_bool", vec![true.into(), false.into()]), "true|false");
+}
+
+#[test]
+fn passes_optional_numbers_with_a_flag() {
+    let some = |arg: Arg| Arg::Option(Some(Box::new(arg)));
+    assert_eq!(
+        echo("echo_option", vec![some(3.into()), some(4i64.into()), some(0.5f64.into()), some(true.into())]),
+        "Some(3)|Some(4)|Some(0.5)|Some(true)"
+    );
+    assert_eq!(
+        echo("echo_option", vec![Arg::Option(None), Arg::Option(None), Arg::Option(None), Arg::Option(None)]),
+        "None|None|None|None"
+    );
+}
+
+#[test]
+fn passes_other_values_as_host_pointers() {
+    let query = Value::from(json!({ "query": "naruto", "page": "2", "filters": [] }));
+    assert_eq!(echo("echo_ptr", vec![query.into()]), r#"naruto|Some("2")"#);
+}
+
+#[test]
+fn passes_absent_values_as_null_pointers() {
+    let query = Value::from(json!({ "query": "naruto", "filters": [] }));
+    assert_eq!(echo("echo_option_ptr", vec![query.into()]), r#"Some("naruto")"#);
+    assert_eq!(echo("echo_option_ptr", vec![Arg::NullPtr]), "None");
+}
+
+#[test]
+fn returns_errors_with_their_details() {
+    let mut runner = Runner::new(fixture()).unwrap();
+    let error = runner.call("fail", vec![503.into(), "https://fixture.to/down".into()]).unwrap();
+    assert_eq!(
+        error.to_json(),
+        json!({
+            "error": 6,
+            "message": "while failing on purpose: request to https://fixture.to/down failed with status 503",
+            "cause": "request to https://fixture.to/down failed with status 503",
+            "context": ["while failing on purpose"],
+            "status": 503,
+            "url": "https://fixture.to/down",
+        })
+    );
+}
+
+#[test]
+fn rejects_arguments_that_do_not_match_the_export() {
+    let mut runner = Runner::new(fixture()).unwrap();
+    let error = runner.call("echo_str", vec!["a".into()]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`echo_str` takes (i32, i32, i32, i32), but the arguments are passed as (i32, i32)"
+    );
+    let error = runner.call("echo_i64", vec![1.into(), 2i64.into()]).unwrap_err();
+    assert!(error.to_string().contains("passed as (i32, i64)"), "{}", error);
+    assert!(runner.call("echo_option", vec![Arg::Option(Some(Box::new("a".into())))]).is_err());
+    assert!(runner.call("missing", vec![]).is_err());
+}
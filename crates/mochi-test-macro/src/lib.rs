@@ -6,10 +6,26 @@ mod parser;
 
 use proc_macro::TokenStream;
 
+/// Turns a function into a `#[test]` that runs against a fresh emulated host,
+/// after `mochi::test::reset()`.
+///
+/// The function may return `()` or any `Result` the test harness accepts.
+///
+/// Used as `mochi::test::mochi_test`, which needs the `test-host` feature of
+/// `mochi-rs`, usually enabled in `[dev-dependencies]`.
+///
+/// `#[mochi_test(fixtures = "tests/fixtures/name")]` serves the test's HTTP
+/// requests from that directory, relative to the crate root. See
+/// `mochi_test::fixtures` for recording them.
 #[proc_macro_attribute]
 pub fn mochi_test(
-    _attr: TokenStream, 
+    attr: TokenStream, 
     input: TokenStream
 ) -> TokenStream {
-    input.into()
-}
\ No newline at end of file
+    match parser::expand(attr.into(), input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            error.to_compile_error().into()
+        }
+    }
+}
@@ -1,4 +1,76 @@
-// extern crate proc_macro2;
-// extern crate quote;
-// extern crate syn;
-// extern crate alloc;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::Parser, punctuated::Punctuated, spanned::Spanned, Error, Expr, ExprLit, ItemFn, Lit,
+    LitStr, MetaNameValue, Result, Token,
+};
+
+#[derive(Default)]
+struct Args {
+    fixtures: Option<LitStr>,
+}
+
+fn parse_args(attr: TokenStream) -> Result<Args> {
+    let mut args = Args::default();
+    let metas = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(attr)?;
+    for meta in metas {
+        if meta.path.is_ident("fixtures") {
+            match meta.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(path), .. }) => args.fixtures = Some(path),
+                value => return Err(Error::new(value.span(), "expected a path to the fixtures directory")),
+            }
+        } else {
+            return Err(Error::new(meta.path.span(), "unknown [mochi_test] argument, expected `fixtures`"));
+        }
+    }
+    Ok(args)
+}
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    let args = parse_args(attr)?;
+
+    let item = syn::parse2::<ItemFn>(input)?;
+    validate(&item)?;
+
+    let ItemFn { attrs, vis, sig, block } = item;
+    let stmts = &block.stmts;
+
+    // Fixture paths are relative to the crate being tested.
+    let fixtures = args.fixtures.map(|path| {
+        quote! {
+            mochi::test::fixtures::Fixtures::new(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)
+            ).install();
+        }
+    });
+
+    Ok(
+        quote! {
+            #[test]
+            #(#attrs)*
+            #vis #sig {
+                mochi::test::reset();
+                #fixtures
+                #(#stmts)*
+            }
+        }
+    )
+}
+
+fn validate(item: &ItemFn) -> Result<()> {
+    let sig = &item.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new(asyncness.span(), "[mochi_test] functions cannot be async"));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new(sig.generics.span(), "[mochi_test] functions cannot be generic"));
+    }
+    if !sig.inputs.is_empty() {
+        return Err(Error::new(sig.inputs.span(), "[mochi_test] functions cannot take arguments"));
+    }
+    Ok(())
+}
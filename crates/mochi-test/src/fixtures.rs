@@ -0,0 +1,265 @@
+//! Record/replay of HTTP requests sent through the emulated host.
+//!
+//! Each request is stored as a JSON file in a fixtures directory, named after
+//! a hash of its method, URL, headers and body. Headers that change between
+//! runs, `Cookie` and any added with [Fixtures::ignore_header], are left
+//! out. In [Mode::Replay] requests are only served from these files, so tests
+//! run offline and deterministically.
+//! In [Mode::Record] requests are sent for real and their responses written
+//! back, optionally through a local stand-in [Server](crate::server::Server).
+//!
+//! ```ignore
+//! #[mochi_test(fixtures = "tests/fixtures/search")]
+//! fn search_finds_naruto() {
+//!     let paging = to_host(MySource::search(query));
+//!     assert!(paging.get("items").is_some());
+//! }
+//! ```
+//!
+//! Run the tests with `MOCHI_RECORD=1` to (re)capture the fixtures.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::host::http::{fetch, RequestState, Response};
+
+/// Environment variable switching [Fixtures::new] to [Mode::Record].
+pub const RECORD_ENV: &str = "MOCHI_RECORD";
+
+/// Request headers always left out of fixtures. Cookies are set by earlier
+/// responses, so they differ between a recording and its replay.
+pub const IGNORED_HEADERS: &[&str] = &["cookie"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Serve requests from the fixtures directory, panicking on a miss.
+    Replay,
+    /// Send requests and write their responses to the fixtures directory.
+    Record,
+}
+
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: Mode,
+    redirects: Vec<(String, String)>,
+    ignored_headers: Vec<String>,
+}
+
+impl Fixtures {
+    /// Replays from `dir`, or records to it when `MOCHI_RECORD` is set.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        match std::env::var(RECORD_ENV) {
+            Ok(value) if !value.is_empty() && value != "0" => Self::record(dir),
+            _ => Self::replay(dir),
+        }
+    }
+
+    pub fn replay<P: AsRef<Path>>(dir: P) -> Self {
+        Self::with_mode(dir, Mode::Replay)
+    }
+
+    pub fn record<P: AsRef<Path>>(dir: P) -> Self {
+        Self::with_mode(dir, Mode::Record)
+    }
+
+    fn with_mode<P: AsRef<Path>>(dir: P, mode: Mode) -> Self {
+        Self {
+            dir: dir.as_ref().to_owned(),
+            mode,
+            redirects: Vec::new(),
+            ignored_headers: IGNORED_HEADERS.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// While recording, send requests for URLs starting with `prefix` to
+    /// `target` instead, e.g. a local [Server](crate::server::Server).
+    /// Fixtures are still keyed by the original URL.
+    pub fn redirect<A: Into<String>, B: Into<String>>(mut self, prefix: A, target: B) -> Self {
+        self.redirects.push((prefix.into(), target.into()));
+        self
+    }
+
+    /// Leave the header `name` out of fixtures, for values that change
+    /// between runs such as tokens. [IGNORED_HEADERS] are always left out.
+    pub fn ignore_header<T: Into<String>>(mut self, name: T) -> Self {
+        self.ignored_headers.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Serve the current thread's HTTP requests with these fixtures until the
+    /// host is [reset](crate::host::reset).
+    pub fn install(self) {
+        crate::host::with(|host| host.set_fixtures(Some(self)));
+    }
+
+    /// Path of the fixture `request` is stored in.
+    pub fn path(&self, request: &RequestState) -> PathBuf {
+        self.dir.join(file_name(request, &self.key(request)))
+    }
+
+    pub(crate) fn send(&self, request: &RequestState) -> Option<Response> {
+        let path = self.path(request);
+        match self.mode {
+            Mode::Replay => match fs::read(&path) {
+                Ok(contents) => Some(parse_fixture(&contents).unwrap_or_else(|| {
+                    panic!("invalid HTTP fixture {}", path.display())
+                })),
+                Err(_) => panic!(
+                    "no HTTP fixture for {} {} (expected {}); run with {}=1 to record it",
+                    request.method.as_str(),
+                    request.url.as_deref().unwrap_or_default(),
+                    path.display(),
+                    RECORD_ENV
+                ),
+            },
+            Mode::Record => {
+                let response = self.fetch(request)?;
+                let contents = serde_json::to_vec_pretty(&fixture(request, &self.headers(request), &response))
+                    .expect("fixtures are valid JSON");
+                if let Err(error) = fs::create_dir_all(&self.dir).and_then(|_| fs::write(&path, contents)) {
+                    panic!("failed to write HTTP fixture {}: {}", path.display(), error);
+                }
+                Some(response)
+            }
+        }
+    }
+
+    /// The request's headers that are part of its fixture, lowercased and
+    /// sorted so they're compared case-insensitively and in any order.
+    fn headers(&self, request: &RequestState) -> Vec<(String, String)> {
+        let mut headers: Vec<_> = request
+            .headers
+            .iter()
+            .map(|(key, value)| (key.to_ascii_lowercase(), value.clone()))
+            .filter(|(key, _)| !self.ignored_headers.contains(key))
+            .collect();
+        headers.sort();
+        headers
+    }
+
+    /// Hash of everything that identifies a request.
+    fn key(&self, request: &RequestState) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(request.method.as_str());
+        hasher.update("\n");
+        hasher.update(request.url.as_deref().unwrap_or_default());
+        hasher.update("\n");
+        for (key, value) in self.headers(request) {
+            hasher.update(format!("{}: {}\n", key, value));
+        }
+        hasher.update("\n");
+        if let Some(body) = &request.body {
+            hasher.update(body);
+        }
+        hasher.finalize()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn fetch(&self, request: &RequestState) -> Option<Response> {
+        let url = request.url.as_deref()?;
+        let redirect = self
+            .redirects
+            .iter()
+            .find(|(prefix, _)| url.starts_with(prefix.as_str()));
+
+        match redirect {
+            Some((prefix, target)) => {
+                let mut redirected = request.clone();
+                redirected.url = Some(format!("{}{}", target, &url[prefix.len()..]));
+                let mut response = fetch(&redirected)?;
+                if let Some(path) = response.url.strip_prefix(target.as_str()) {
+                    response.url = format!("{}{}", prefix, path);
+                }
+                Some(response)
+            }
+            None => fetch(request),
+        }
+    }
+}
+
+/// `<method>-<url>-<hash>.json`, with the URL shortened to something readable.
+fn file_name(request: &RequestState, key: &str) -> String {
+    let url = request.url.as_deref().unwrap_or_default();
+    let readable: String = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .take(64)
+        .collect();
+    format!(
+        "{}-{}-{}.json",
+        request.method.as_str().to_ascii_lowercase(),
+        readable.trim_end_matches('_'),
+        key
+    )
+}
+
+fn headers_json(headers: &[(String, String)]) -> serde_json::Value {
+    headers.iter().map(|(key, value)| json!([key, value])).collect()
+}
+
+/// Bodies are stored as text when possible so fixtures can be reviewed.
+fn body_json(object: &mut serde_json::Value, body: &[u8]) {
+    match std::str::from_utf8(body) {
+        Ok(text) => object["body"] = json!(text),
+        Err(_) => object["bodyBase64"] = json!(BASE64.encode(body)),
+    }
+}
+
+fn fixture(request: &RequestState, headers: &[(String, String)], response: &Response) -> serde_json::Value {
+    let mut request_json = json!({
+        "method": request.method.as_str(),
+        "url": request.url,
+        "headers": headers_json(headers),
+    });
+    if let Some(body) = &request.body {
+        body_json(&mut request_json, body);
+    }
+
+    let mut response_json = json!({
+        "status": response.status,
+        "url": response.url,
+        "headers": headers_json(&response.headers),
+    });
+    body_json(&mut response_json, &response.body);
+
+    json!({ "request": request_json, "response": response_json })
+}
+
+fn parse_fixture(contents: &[u8]) -> Option<Response> {
+    let fixture: serde_json::Value = serde_json::from_slice(contents).ok()?;
+    let response = fixture.get("response")?;
+
+    let headers = response
+        .get("headers")?
+        .as_array()?
+        .iter()
+        .map(|header| Some((header.get(0)?.as_str()?.to_owned(), header.get(1)?.as_str()?.to_owned())))
+        .collect::<Option<_>>()?;
+    let body = match (response.get("body"), response.get("bodyBase64")) {
+        (Some(text), _) => text.as_str()?.as_bytes().to_vec(),
+        (None, Some(data)) => BASE64.decode(data.as_str()?).ok()?,
+        (None, None) => Vec::new(),
+    };
+
+    Some(Response {
+        status: response.get("status")?.as_i64()? as i32,
+        url: response.get("url")?.as_str()?.to_owned(),
+        headers,
+        body,
+    })
+}
@@ -0,0 +1,204 @@
+//! In-process implementation of the `core`, `json`, `html`, `http`, `crypto`,
+//! `settings`, `storage`, `structs_meta` and `structs_video` import modules.
+//!
+//! Every value the host hands out lives in a thread-local handle table and is
+//! referenced by a [HostPtr]. Each test thread gets its own table, which can be
+//! cleared with [reset].
+//!
+//! # Safety
+//! The functions in the import modules share the contract of the wasm imports
+//! they replace: every pointer/length pair must describe memory that is valid
+//! for reads, or for writes when the host fills a buffer.
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::fixtures::Fixtures;
+
+mod value;
+pub use value::Value;
+
+pub mod core;
+pub mod crypto;
+pub mod html;
+pub mod http;
+pub mod json;
+pub mod settings;
+pub mod storage;
+pub mod structs_meta;
+pub mod structs_video;
+
+pub type HostPtr = i32;
+
+/// The state of an emulated host.
+#[derive(Debug, Default)]
+pub struct Host {
+    values: HashMap<HostPtr, Value>,
+    next_ptr: HostPtr,
+    logs: Vec<String>,
+    fixtures: Option<Fixtures>,
+    cookies: Option<Value>,
+    cache: HashMap<String, Value>,
+    source: String,
+    storage: HashMap<String, BTreeMap<String, Value>>,
+    settings: HashMap<String, BTreeMap<String, Value>>,
+}
+
+thread_local! {
+    static HOST: RefCell<Host> = RefCell::new(Host::default());
+}
+
+impl Host {
+    /// Store a value and return the pointer referencing it.
+    pub fn insert(&mut self, value: Value) -> HostPtr {
+        let ptr = self.next_ptr;
+        self.next_ptr += 1;
+        self.values.insert(ptr, value);
+        ptr
+    }
+
+    pub fn get(&self, ptr: HostPtr) -> Option<&Value> {
+        self.values.get(&ptr)
+    }
+
+    pub fn get_mut(&mut self, ptr: HostPtr) -> Option<&mut Value> {
+        self.values.get_mut(&ptr)
+    }
+
+    /// Remove a value from the table, returning it.
+    pub fn take(&mut self, ptr: HostPtr) -> Option<Value> {
+        self.values.remove(&ptr)
+    }
+
+    /// Number of pointers that are still alive.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Messages printed by the module, in order.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Fixtures serving HTTP requests, if any. Otherwise requests are sent
+    /// over the network.
+    pub fn fixtures(&self) -> Option<&Fixtures> {
+        self.fixtures.as_ref()
+    }
+
+    pub fn set_fixtures(&mut self, fixtures: Option<Fixtures>) {
+        self.fixtures = fixtures;
+    }
+
+    /// Cookies saved by the module with `CookieJar::save`.
+    pub fn cookies(&self) -> Option<&Value> {
+        self.cookies.as_ref()
+    }
+
+    pub fn set_cookies(&mut self, cookies: Option<Value>) {
+        self.cookies = cookies;
+    }
+
+    /// Responses cached by the module with `Cache::persist`.
+    pub fn cache(&self) -> &HashMap<String, Value> {
+        &self.cache
+    }
+
+    pub fn cache_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.cache
+    }
+
+    /// The source whose storage and settings the imports use. Empty by default.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Switches to the storage of another source, as when the host loads it.
+    pub fn set_source<T: Into<String>>(&mut self, source: T) {
+        self.source = source.into();
+    }
+
+    /// Values stored by the current source.
+    pub fn storage(&self) -> Option<&BTreeMap<String, Value>> {
+        self.storage.get(&self.source)
+    }
+
+    pub fn storage_mut(&mut self) -> &mut BTreeMap<String, Value> {
+        self.storage.entry(self.source.clone()).or_default()
+    }
+
+    /// Settings the user picked for the current source.
+    pub fn settings(&self) -> Option<&BTreeMap<String, Value>> {
+        self.settings.get(&self.source)
+    }
+
+    /// Picks `value` for the setting `id` of the current source.
+    pub fn set_setting<A: Into<String>, B: Into<Value>>(&mut self, id: A, value: B) {
+        self.settings.entry(self.source.clone()).or_default().insert(id.into(), value.into());
+    }
+
+    pub(crate) fn log(&mut self, message: String) {
+        self.logs.push(message);
+    }
+}
+
+/// Run `f` with the current thread's host.
+///
+/// # Panics
+/// Panics if called re-entrantly from within `f`.
+pub fn with<R>(f: impl FnOnce(&mut Host) -> R) -> R {
+    HOST.with(|host| f(&mut host.borrow_mut()))
+}
+
+/// Drop every value and log of the current thread's host.
+pub fn reset() {
+    with(|host| *host = Host::default())
+}
+
+/// Store a value in the current thread's host.
+pub fn insert(value: Value) -> HostPtr {
+    with(|host| host.insert(value))
+}
+
+/// Get a copy of the value behind `ptr`.
+pub fn value(ptr: HostPtr) -> Option<Value> {
+    with(|host| host.get(ptr).cloned())
+}
+
+/// Same as [value], but removes the pointer from the table.
+pub fn take(ptr: HostPtr) -> Option<Value> {
+    with(|host| host.take(ptr))
+}
+
+pub(crate) unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 || ptr.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+pub(crate) unsafe fn string(ptr: *const u8, len: usize) -> String {
+    String::from_utf8_lossy(bytes(ptr, len)).into_owned()
+}
+
+/// Reads a string that the module passes as `(-1, -1)` when absent.
+pub(crate) unsafe fn optional_string(ptr: *const u8, len: i32) -> Option<String> {
+    if len < 0 {
+        None
+    } else {
+        Some(string(ptr, len as usize))
+    }
+}
+
+pub(crate) unsafe fn write_bytes(dst: *mut u8, src: &[u8], len: i32) {
+    let len = src.len().min(len.max(0) as usize);
+    if len > 0 {
+        std::ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+    }
+}
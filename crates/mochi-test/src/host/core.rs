@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use super::value::KIND_NULL;
+use super::{bytes, string, with, write_bytes, HostPtr, Value};
+
+pub unsafe fn copy(ptr: HostPtr) -> HostPtr {
+    with(|host| match host.get(ptr).cloned() {
+        Some(value) => host.insert(value),
+        None => -1,
+    })
+}
+
+pub unsafe fn destroy(ptr: HostPtr) {
+    with(|host| host.take(ptr));
+}
+
+pub unsafe fn create_array() -> HostPtr {
+    super::insert(Value::Array(Vec::new()))
+}
+
+pub unsafe fn create_obj() -> HostPtr {
+    super::insert(Value::Object(BTreeMap::new()))
+}
+
+pub unsafe fn create_null() -> HostPtr {
+    super::insert(Value::Null)
+}
+
+pub unsafe fn create_string(buf_raw_ptr: *const u8, buf_len: i32) -> HostPtr {
+    let value = string(buf_raw_ptr, buf_len.max(0) as usize);
+    super::insert(Value::String(value))
+}
+
+pub unsafe fn create_bool(value: bool) -> HostPtr {
+    super::insert(Value::Bool(value))
+}
+
+pub unsafe fn create_float(value: f64) -> HostPtr {
+    super::insert(Value::Float(value))
+}
+
+pub unsafe fn create_int(value: i64) -> HostPtr {
+    super::insert(Value::Int(value))
+}
+
+/// An error with a message and the fields of the object at `details`, which
+/// stays owned by the caller.
+pub unsafe fn create_error_with(kind: i32, msg_ptr: *const u8, msg_len: i32, details: HostPtr) -> HostPtr {
+    let message = string(msg_ptr, msg_len.max(0) as usize);
+    let details = with(|host| host.get(details).and_then(Value::as_object).cloned()).unwrap_or_default();
+    super::insert(Value::Error { kind, message, details })
+}
+
+/// Pointers the host doesn't know about are reported as `Kind::Null`.
+pub unsafe fn ptr_kind(ptr: HostPtr) -> i32 {
+    with(|host| host.get(ptr).map(Value::kind).unwrap_or(KIND_NULL))
+}
+
+pub unsafe fn string_len(ptr: HostPtr) -> i32 {
+    with(|host| {
+        host.get(ptr)
+            .and_then(Value::as_str)
+            .map(|value| value.len() as i32)
+            .unwrap_or(0)
+    })
+}
+
+pub unsafe fn read_string(ptr: HostPtr, buf_raw_ptr: *mut u8, buf_len: i32) {
+    with(|host| {
+        if let Some(value) = host.get(ptr).and_then(Value::as_str) {
+            write_bytes(buf_raw_ptr, value.as_bytes(), buf_len);
+        }
+    })
+}
+
+pub unsafe fn read_int(ptr: HostPtr) -> i64 {
+    with(|host| host.get(ptr).and_then(Value::as_i64).unwrap_or_default())
+}
+
+pub unsafe fn read_float(ptr: HostPtr) -> f64 {
+    with(|host| host.get(ptr).and_then(Value::as_f64).unwrap_or_default())
+}
+
+pub unsafe fn read_bool(ptr: HostPtr) -> bool {
+    with(|host| host.get(ptr).and_then(Value::as_bool).unwrap_or_default())
+}
+
+pub unsafe fn obj_len(ptr: HostPtr) -> usize {
+    with(|host| host.get(ptr).and_then(Value::as_object).map(BTreeMap::len).unwrap_or(0))
+}
+
+pub unsafe fn obj_get(ptr: HostPtr, key_raw_ptr: *const u8, len: usize) -> HostPtr {
+    let key = string(key_raw_ptr, len);
+    with(|host| match host.get(ptr).and_then(|value| value.get(&key)).cloned() {
+        Some(value) => host.insert(value),
+        None => -1,
+    })
+}
+
+/// The module keeps ownership of `value_ptr`, so the value is copied in.
+pub unsafe fn obj_set(ptr: HostPtr, key_raw_ptr: *const u8, len: usize, value_ptr: HostPtr) {
+    let key = string(key_raw_ptr, len);
+    with(|host| {
+        let value = host.get(value_ptr).cloned().unwrap_or(Value::Null);
+        if let Some(Value::Object(object)) = host.get_mut(ptr) {
+            object.insert(key, value);
+        }
+    })
+}
+
+pub unsafe fn obj_remove(ptr: HostPtr, key_raw_ptr: *const u8, len: usize) {
+    let key = string(key_raw_ptr, len);
+    with(|host| {
+        if let Some(Value::Object(object)) = host.get_mut(ptr) {
+            object.remove(&key);
+        }
+    })
+}
+
+pub unsafe fn obj_keys(ptr: HostPtr) -> HostPtr {
+    with(|host| {
+        let keys = host
+            .get(ptr)
+            .and_then(Value::as_object)
+            .map(|object| object.keys().cloned().map(Value::String).collect())
+            .unwrap_or_default();
+        host.insert(Value::Array(keys))
+    })
+}
+
+pub unsafe fn obj_values(ptr: HostPtr) -> HostPtr {
+    with(|host| {
+        let values = host
+            .get(ptr)
+            .and_then(Value::as_object)
+            .map(|object| object.values().cloned().collect())
+            .unwrap_or_default();
+        host.insert(Value::Array(values))
+    })
+}
+
+pub unsafe fn array_len(ptr: HostPtr) -> i32 {
+    with(|host| host.get(ptr).and_then(Value::as_array).map(|a| a.len() as i32).unwrap_or(0))
+}
+
+pub unsafe fn array_get(ptr: HostPtr, idx: i32) -> i32 {
+    with(|host| {
+        let value = host
+            .get(ptr)
+            .and_then(Value::as_array)
+            .and_then(|array| array.get(usize::try_from(idx).ok()?))
+            .cloned();
+        match value {
+            Some(value) => host.insert(value),
+            None => -1,
+        }
+    })
+}
+
+pub unsafe fn array_set(ptr: HostPtr, idx: i32, value_ptr: i32) {
+    with(|host| {
+        let value = host.get(value_ptr).cloned().unwrap_or(Value::Null);
+        if let (Some(Value::Array(array)), Ok(idx)) = (host.get_mut(ptr), usize::try_from(idx)) {
+            if let Some(slot) = array.get_mut(idx) {
+                *slot = value;
+            }
+        }
+    })
+}
+
+pub unsafe fn array_append(ptr: HostPtr, value_ptr: i32) {
+    with(|host| {
+        let value = host.get(value_ptr).cloned().unwrap_or(Value::Null);
+        if let Some(Value::Array(array)) = host.get_mut(ptr) {
+            array.push(value);
+        }
+    })
+}
+
+pub unsafe fn array_remove(ptr: HostPtr, idx: i32) {
+    with(|host| {
+        if let (Some(Value::Array(array)), Ok(idx)) = (host.get_mut(ptr), usize::try_from(idx)) {
+            if idx < array.len() {
+                array.remove(idx);
+            }
+        }
+    })
+}
+
+/// Prints to stdout, which `cargo test` captures per test, and keeps the
+/// message in [Host::logs](super::Host::logs).
+pub unsafe fn print(string: *const u8, size: usize) {
+    let message = String::from_utf8_lossy(bytes(string, size)).into_owned();
+    println!("{}", message);
+    with(|host| host.log(message));
+}
+
+/// Same as [print], with the message prefixed by its level, e.g.
+/// `[warn] message`. Levels are `mochi::std::log::Level`.
+pub unsafe fn log(level: i32, string: *const u8, size: usize) {
+    let level = match level {
+        0 => "debug",
+        1 => "info",
+        2 => "warn",
+        3 => "error",
+        _ => "log",
+    };
+    let message = format!("[{}] {}", level, String::from_utf8_lossy(bytes(string, size)));
+    println!("{}", message);
+    with(|host| host.log(message));
+}
+
+/// Blocks the current thread.
+pub unsafe fn sleep(millis: i32) {
+    std::thread::sleep(std::time::Duration::from_millis(millis.max(0) as u64));
+}
+
+/// Milliseconds since the Unix epoch.
+pub unsafe fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|time| time.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
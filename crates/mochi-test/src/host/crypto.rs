@@ -0,0 +1,151 @@
+//! CryptoJS-compatible primitives: AES is CBC with PKCS#7 padding, and the
+//! key size picks AES-128/192/256.
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use md5::{Digest, Md5};
+
+use super::{bytes, with, write_bytes, HostPtr, Value};
+
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+fn insert_data(data: Option<Vec<u8>>) -> HostPtr {
+    match data {
+        Some(data) => super::insert(Value::Data(data)),
+        None => -1,
+    }
+}
+
+unsafe fn slice<'a>(ptr: *const u8, len: i32) -> &'a [u8] {
+    bytes(ptr, len.max(0) as usize)
+}
+
+pub unsafe fn crypto_get_data_len(host_ptr: i32) -> i32 {
+    with(|host| host.get(host_ptr).and_then(Value::as_data).map(|d| d.len() as i32).unwrap_or(0))
+}
+
+pub unsafe fn crypto_get_data(host_ptr: i32, buf_ptr: *mut u8, buf_len: i32) {
+    with(|host| {
+        if let Some(data) = host.get(host_ptr).and_then(Value::as_data) {
+            write_bytes(buf_ptr, data, buf_len);
+        }
+    })
+}
+
+pub unsafe fn crypto_base64_parse(value_ptr: *const u8, value_len: i32) -> i32 {
+    let value: Vec<u8> = slice(value_ptr, value_len)
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    insert_data(BASE64.decode(value).ok())
+}
+
+pub unsafe fn crypto_base64_string(bytes_ptr: *const u8, bytes_len: i32) -> i32 {
+    super::insert(Value::String(BASE64.encode(slice(bytes_ptr, bytes_len))))
+}
+
+pub unsafe fn crypto_utf8_parse(value_ptr: *const u8, value_len: i32) -> i32 {
+    insert_data(Some(slice(value_ptr, value_len).to_vec()))
+}
+
+pub unsafe fn crypto_pbkdf2(
+    hash_algorithm: i32,
+    password_ptr: *const u8,
+    password_len: i32,
+    salt_ptr: *const u8,
+    salt_len: i32,
+    rounds: i32,
+    key_len: i32,
+) -> i32 {
+    let password = slice(password_ptr, password_len);
+    let salt = slice(salt_ptr, salt_len);
+    let rounds = rounds.max(1) as u32;
+    let mut key = vec![0u8; key_len.max(0) as usize];
+    match hash_algorithm {
+        1 => pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, salt, rounds, &mut key),
+        2 => pbkdf2::pbkdf2_hmac::<sha2::Sha224>(password, salt, rounds, &mut key),
+        3 => pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, salt, rounds, &mut key),
+        4 => pbkdf2::pbkdf2_hmac::<sha2::Sha384>(password, salt, rounds, &mut key),
+        5 => pbkdf2::pbkdf2_hmac::<sha2::Sha512>(password, salt, rounds, &mut key),
+        _ => return -1,
+    }
+    insert_data(Some(key))
+}
+
+pub unsafe fn crypto_generate_random_bytes(count: i32) -> i32 {
+    let mut data = vec![0u8; count.max(0) as usize];
+    insert_data(getrandom::getrandom(&mut data).ok().map(|_| data))
+}
+
+fn aes_encrypt(msg: &[u8], key: &[u8], iv: &[u8]) -> Option<Vec<u8>> {
+    Some(match key.len() {
+        16 => cbc::Encryptor::<aes::Aes128>::new_from_slices(key, iv)
+            .ok()?
+            .encrypt_padded_vec_mut::<Pkcs7>(msg),
+        24 => cbc::Encryptor::<aes::Aes192>::new_from_slices(key, iv)
+            .ok()?
+            .encrypt_padded_vec_mut::<Pkcs7>(msg),
+        32 => cbc::Encryptor::<aes::Aes256>::new_from_slices(key, iv)
+            .ok()?
+            .encrypt_padded_vec_mut::<Pkcs7>(msg),
+        _ => return None,
+    })
+}
+
+fn aes_decrypt(msg: &[u8], key: &[u8], iv: &[u8]) -> Option<Vec<u8>> {
+    match key.len() {
+        16 => cbc::Decryptor::<aes::Aes128>::new_from_slices(key, iv)
+            .ok()?
+            .decrypt_padded_vec_mut::<Pkcs7>(msg)
+            .ok(),
+        24 => cbc::Decryptor::<aes::Aes192>::new_from_slices(key, iv)
+            .ok()?
+            .decrypt_padded_vec_mut::<Pkcs7>(msg)
+            .ok(),
+        32 => cbc::Decryptor::<aes::Aes256>::new_from_slices(key, iv)
+            .ok()?
+            .decrypt_padded_vec_mut::<Pkcs7>(msg)
+            .ok(),
+        _ => None,
+    }
+}
+
+pub unsafe fn crypto_aes_encrypt(
+    msg_ptr: *const u8,
+    msg_len: i32,
+    key_ptr: *const u8,
+    key_len: i32,
+    iv_ptr: *const u8,
+    iv_len: i32,
+) -> i32 {
+    insert_data(aes_encrypt(
+        slice(msg_ptr, msg_len),
+        slice(key_ptr, key_len),
+        slice(iv_ptr, iv_len),
+    ))
+}
+
+pub unsafe fn crypto_aes_decrypt(
+    encrypted_msg_ptr: *const u8,
+    encrypted_msg_len: i32,
+    key_ptr: *const u8,
+    key_len: i32,
+    iv_ptr: *const u8,
+    iv_len: i32,
+) -> i32 {
+    insert_data(aes_decrypt(
+        slice(encrypted_msg_ptr, encrypted_msg_len),
+        slice(key_ptr, key_len),
+        slice(iv_ptr, iv_len),
+    ))
+}
+
+pub unsafe fn crypto_md5_hash(input_ptr: *const u8, input_len: i32) -> i32 {
+    insert_data(Some(Md5::digest(slice(input_ptr, input_len)).to_vec()))
+}
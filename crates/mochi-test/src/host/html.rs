@@ -0,0 +1,540 @@
+use std::fmt::{self, Debug};
+
+use kuchikiki::traits::TendrilSink;
+use kuchikiki::{NodeData, NodeRef};
+
+use super::{bytes, string, with, HostPtr, Value};
+
+/// A document, an element, or a list of elements returned by `select`.
+#[derive(Clone)]
+pub struct Nodes {
+    nodes: Vec<NodeRef>,
+    /// The parsed document, which has to outlive its elements: dropping it
+    /// detaches them from their parents and siblings.
+    document: NodeRef,
+    base_uri: Option<String>,
+}
+
+impl Nodes {
+    fn parse(html: &[u8], base_uri: Option<String>) -> Self {
+        let document = kuchikiki::parse_html().one(String::from_utf8_lossy(html).into_owned());
+        let base_href = document
+            .select_first("base[href]")
+            .ok()
+            .and_then(|base| base.attributes.borrow().get("href").map(str::to_owned));
+        let base_uri = match (base_uri, base_href) {
+            (Some(base), Some(href)) => Some(resolve(&base, &href).unwrap_or(href)),
+            (base, href) => base.or(href),
+        };
+        Self {
+            nodes: vec![document.clone()],
+            document,
+            base_uri,
+        }
+    }
+
+    fn with_nodes(&self, nodes: Vec<NodeRef>) -> Self {
+        Self {
+            nodes,
+            document: self.document.clone(),
+            base_uri: self.base_uri.clone(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[NodeRef] {
+        &self.nodes
+    }
+
+    pub fn base_uri(&self) -> Option<&str> {
+        self.base_uri.as_deref()
+    }
+
+    pub fn select(&self, selector: &str) -> Option<Self> {
+        let mut found: Vec<NodeRef> = Vec::new();
+        for node in &self.nodes {
+            for element in node.select(selector).ok()? {
+                let element = element.as_node().clone();
+                if !found.contains(&element) {
+                    found.push(element);
+                }
+            }
+        }
+        Some(self.with_nodes(found))
+    }
+
+    /// The attribute of the first element that has it. `abs:` prefixed keys
+    /// are resolved against the base URI.
+    pub fn attr(&self, name: &str) -> Option<String> {
+        if let Some(name) = name.strip_prefix("abs:") {
+            let value = self.attr(name)?;
+            return match &self.base_uri {
+                Some(base) => resolve(base, &value),
+                None => url::Url::parse(&value).ok().map(String::from),
+            };
+        }
+        self.nodes.iter().find_map(|node| {
+            let element = node.as_element()?;
+            let attributes = element.attributes.borrow();
+            attributes
+                .map
+                .iter()
+                .find(|(key, _)| key.local.as_ref().eq_ignore_ascii_case(name))
+                .map(|(_, attr)| attr.value.clone())
+        })
+    }
+
+    /// Whitespace-normalized text, skipping `<script>` and `<style>` contents.
+    pub fn text(&self) -> String {
+        normalize(&self.untrimmed_text())
+    }
+
+    pub fn untrimmed_text(&self) -> String {
+        let mut text = String::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                text.push(' ');
+            }
+            collect_text(node, &mut text);
+        }
+        text
+    }
+
+    pub fn own_text(&self) -> String {
+        let text: String = self
+            .nodes
+            .first()
+            .map(|node| {
+                node.children()
+                    .filter_map(|child| child.as_text().map(|text| text.borrow().clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        normalize(&text)
+    }
+
+    pub fn data(&self) -> String {
+        let mut data = String::new();
+        for node in &self.nodes {
+            for descendant in node.inclusive_descendants() {
+                match descendant.data() {
+                    NodeData::Comment(comment) => data.push_str(&comment.borrow()),
+                    NodeData::Text(text) if is_data_parent(&descendant) => {
+                        data.push_str(&text.borrow())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        data
+    }
+
+    pub fn html(&self) -> String {
+        self.nodes
+            .iter()
+            .map(|node| node.children().map(|child| child.to_string()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn outer_html(&self) -> String {
+        self.nodes
+            .iter()
+            .map(|node| node.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn tag_name(&self) -> Option<String> {
+        let element = self.nodes.first()?.as_element()?;
+        Some(element.name.local.to_ascii_lowercase().to_string())
+    }
+
+    /// The first element, where a document counts as one, as it does in
+    /// SwiftSoup.
+    fn first_element(&self) -> Option<&NodeRef> {
+        self.nodes
+            .iter()
+            .find(|node| node.as_element().is_some() || node.as_document().is_some())
+    }
+
+    fn modify(&self, f: impl FnOnce(&NodeRef)) -> bool {
+        match self.first_element() {
+            Some(node) => {
+                f(node);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Debug for Nodes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Nodes")
+            .field("html", &self.outer_html())
+            .field("base_uri", &self.base_uri)
+            .finish()
+    }
+}
+
+fn resolve(base: &str, href: &str) -> Option<String> {
+    url::Url::parse(base)
+        .and_then(|base| base.join(href))
+        .ok()
+        .map(String::from)
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_data_parent(node: &NodeRef) -> bool {
+    node.parent()
+        .and_then(|parent| parent.as_element().map(|e| e.name.local.clone()))
+        .map(|name| &*name == "script" || &*name == "style")
+        .unwrap_or(false)
+}
+
+fn collect_text(node: &NodeRef, text: &mut String) {
+    for descendant in node.inclusive_descendants() {
+        if let Some(value) = descendant.as_text() {
+            if !is_data_parent(&descendant) {
+                text.push_str(&value.borrow());
+            }
+        }
+    }
+}
+
+/// Parses `html` as the contents of a `<body>`.
+fn parse_children(html: &str) -> Vec<NodeRef> {
+    let document = kuchikiki::parse_html().one(html);
+    match document.select_first("body") {
+        Ok(body) => body.as_node().children().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn nodes(ptr: HostPtr) -> Option<Nodes> {
+    with(|host| match host.get(ptr) {
+        Some(Value::Node(nodes)) => Some(nodes.clone()),
+        _ => None,
+    })
+}
+
+fn insert_nodes(nodes: Option<Nodes>) -> HostPtr {
+    match nodes {
+        Some(nodes) => super::insert(Value::Node(nodes)),
+        None => -1,
+    }
+}
+
+fn insert_string(value: Option<String>) -> HostPtr {
+    super::insert(Value::from(value))
+}
+
+fn node_string(ptr: HostPtr, f: impl FnOnce(&Nodes) -> Option<String>) -> HostPtr {
+    insert_string(nodes(ptr).as_ref().and_then(f))
+}
+
+pub unsafe fn scraper_parse(string: *const u8, len: usize) -> HostPtr {
+    super::insert(Value::Node(Nodes::parse(bytes(string, len), None)))
+}
+
+pub unsafe fn scraper_parse_with_uri(
+    string: *const u8,
+    len: usize,
+    base_uri: *const u8,
+    base_uri_len: usize,
+) -> HostPtr {
+    let base_uri = self::string(base_uri, base_uri_len);
+    super::insert(Value::Node(Nodes::parse(bytes(string, len), Some(base_uri))))
+}
+
+pub unsafe fn scraper_parse_fragment(string: *const u8, len: usize) -> HostPtr {
+    scraper_parse(string, len)
+}
+
+pub unsafe fn scraper_parse_fragment_with_uri(
+    string: *const u8,
+    len: usize,
+    base_uri: *const u8,
+    base_uri_len: usize,
+) -> HostPtr {
+    scraper_parse_with_uri(string, len, base_uri, base_uri_len)
+}
+
+pub unsafe fn scraper_select(ptr: HostPtr, selector: *const u8, selector_len: usize) -> i32 {
+    let selector = string(selector, selector_len);
+    insert_nodes(nodes(ptr).and_then(|nodes| nodes.select(&selector)))
+}
+
+pub unsafe fn scraper_select_first(ptr: HostPtr, selector: *const u8, selector_len: usize) -> HostPtr {
+    let selector = string(selector, selector_len);
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let first = nodes.select(&selector)?.nodes.first()?.clone();
+        Some(nodes.with_nodes(vec![first]))
+    }))
+}
+
+pub unsafe fn scraper_closest(ptr: HostPtr, selector: *const u8, selector_len: usize) -> HostPtr {
+    let selector = string(selector, selector_len);
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let selectors = kuchikiki::Selectors::compile(&selector).ok()?;
+        let closest = nodes
+            .first_element()?
+            .inclusive_ancestors()
+            .filter_map(|node| node.into_element_ref())
+            .find(|element| selectors.matches(element))?;
+        Some(nodes.with_nodes(vec![closest.as_node().clone()]))
+    }))
+}
+
+pub unsafe fn scraper_attr(ptr: HostPtr, selector: *const u8, selector_len: usize) -> i32 {
+    let name = string(selector, selector_len);
+    node_string(ptr, |nodes| nodes.attr(&name))
+}
+
+pub unsafe fn scraper_set_text(ptr: HostPtr, text: *const u8, text_len: usize) -> i32 {
+    let text = string(text, text_len);
+    let modified = nodes(ptr).map(|nodes| {
+        nodes.modify(|node| {
+            node.children().collect::<Vec<_>>().iter().for_each(|child| child.detach());
+            node.append(NodeRef::new_text(text));
+        })
+    });
+    if modified == Some(true) { 0 } else { -1 }
+}
+
+pub unsafe fn scraper_set_html(ptr: HostPtr, html: *const u8, html_len: usize) -> i32 {
+    let html = string(html, html_len);
+    let modified = nodes(ptr).map(|nodes| {
+        nodes.modify(|node| {
+            node.children().collect::<Vec<_>>().iter().for_each(|child| child.detach());
+            parse_children(&html).into_iter().for_each(|child| node.append(child));
+        })
+    });
+    if modified == Some(true) { 0 } else { -1 }
+}
+
+pub unsafe fn scraper_prepend(ptr: HostPtr, html: *const u8, html_len: usize) -> i32 {
+    let html = string(html, html_len);
+    let modified = nodes(ptr).map(|nodes| {
+        nodes.modify(|node| {
+            parse_children(&html).into_iter().rev().for_each(|child| node.prepend(child));
+        })
+    });
+    if modified == Some(true) { 0 } else { -1 }
+}
+
+pub unsafe fn scraper_append(ptr: HostPtr, html: *const u8, html_len: usize) -> i32 {
+    let html = string(html, html_len);
+    let modified = nodes(ptr).map(|nodes| {
+        nodes.modify(|node| {
+            parse_children(&html).into_iter().for_each(|child| node.append(child));
+        })
+    });
+    if modified == Some(true) { 0 } else { -1 }
+}
+
+pub unsafe fn scraper_first(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let first = nodes.nodes.first()?.clone();
+        Some(nodes.with_nodes(vec![first]))
+    }))
+}
+
+pub unsafe fn scraper_last(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let last = nodes.nodes.last()?.clone();
+        Some(nodes.with_nodes(vec![last]))
+    }))
+}
+
+pub unsafe fn scraper_next(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let next = nodes
+            .nodes
+            .first()?
+            .following_siblings()
+            .find(|node| node.as_element().is_some())?;
+        Some(nodes.with_nodes(vec![next]))
+    }))
+}
+
+pub unsafe fn scraper_previous(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let previous = nodes
+            .nodes
+            .first()?
+            .preceding_siblings()
+            .find(|node| node.as_element().is_some())?;
+        Some(nodes.with_nodes(vec![previous]))
+    }))
+}
+
+pub unsafe fn scraper_parent(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).and_then(|nodes| {
+        let parent = nodes.first_element()?.parent()?;
+        parent.as_element()?;
+        Some(nodes.with_nodes(vec![parent]))
+    }))
+}
+
+pub unsafe fn scraper_children(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).map(|nodes| {
+        let children = match nodes.first_element() {
+            Some(node) => node.children().filter(|child| child.as_element().is_some()).collect(),
+            None => Vec::new(),
+        };
+        nodes.with_nodes(children)
+    }))
+}
+
+pub unsafe fn scraper_siblings(ptr: HostPtr) -> HostPtr {
+    insert_nodes(nodes(ptr).map(|nodes| {
+        let siblings = match nodes.first_element() {
+            Some(node) => node
+                .preceding_siblings()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .chain(node.following_siblings())
+                .filter(|sibling| sibling.as_element().is_some())
+                .collect(),
+            None => Vec::new(),
+        };
+        nodes.with_nodes(siblings)
+    }))
+}
+
+pub unsafe fn scraper_index_in_parent(ptr: HostPtr) -> i32 {
+    nodes(ptr)
+        .and_then(|nodes| {
+            let node = nodes.first_element()?;
+            node.parent()?;
+            Some(node.preceding_siblings().filter(|sibling| sibling.as_element().is_some()).count() as i32)
+        })
+        .unwrap_or(-1)
+}
+
+pub unsafe fn scraper_base_uri(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| nodes.base_uri.clone())
+}
+
+pub unsafe fn scraper_body(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| {
+        let body = nodes.select("body")?;
+        (!body.is_empty()).then(|| body.outer_html())
+    })
+}
+
+pub unsafe fn scraper_text(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.text()))
+}
+
+pub unsafe fn scraper_untrimmed_text(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.untrimmed_text()))
+}
+
+pub unsafe fn scraper_own_text(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.own_text()))
+}
+
+pub unsafe fn scraper_data(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.data()))
+}
+
+pub unsafe fn scraper_array(ptr: HostPtr) -> HostPtr {
+    let array = nodes(ptr)
+        .map(|nodes| {
+            nodes
+                .nodes
+                .iter()
+                .map(|node| Value::Node(nodes.with_nodes(vec![node.clone()])))
+                .collect()
+        })
+        .unwrap_or_default();
+    super::insert(Value::Array(array))
+}
+
+pub unsafe fn scraper_html(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.html()))
+}
+
+pub unsafe fn scraper_outer_html(ptr: HostPtr) -> HostPtr {
+    node_string(ptr, |nodes| Some(nodes.outer_html()))
+}
+
+/// Escapes either a string or the text of a node.
+pub unsafe fn scraper_escape(ptr: HostPtr) -> HostPtr {
+    let text = with(|host| match host.get(ptr) {
+        Some(Value::String(value)) => Some(value.clone()),
+        Some(Value::Node(nodes)) => Some(nodes.text()),
+        _ => None,
+    });
+    insert_string(text.map(|text| html_escape::encode_text(&text).into_owned()))
+}
+
+/// Unescapes either a string or the text of a node.
+pub unsafe fn scraper_unescape(ptr: HostPtr) -> HostPtr {
+    let text = with(|host| match host.get(ptr) {
+        Some(Value::String(value)) => Some(value.clone()),
+        Some(Value::Node(nodes)) => Some(nodes.text()),
+        _ => None,
+    });
+    insert_string(text.map(|text| html_escape::decode_html_entities(&text).into_owned()))
+}
+
+pub unsafe fn scraper_id(ptr: HostPtr) -> i32 {
+    node_string(ptr, |nodes| nodes.attr("id"))
+}
+
+pub unsafe fn scraper_tag_name(ptr: HostPtr) -> i32 {
+    node_string(ptr, Nodes::tag_name)
+}
+
+pub unsafe fn scraper_class_name(ptr: HostPtr) -> i32 {
+    node_string(ptr, |nodes| nodes.attr("class"))
+}
+
+pub unsafe fn scraper_has_class(ptr: HostPtr, class_name: *const u8, class_length: usize) -> bool {
+    let class_name = string(class_name, class_length);
+    nodes(ptr)
+        .and_then(|nodes| nodes.attr("class"))
+        .map(|classes| {
+            classes
+                .split_whitespace()
+                .any(|class| class.eq_ignore_ascii_case(&class_name))
+        })
+        .unwrap_or(false)
+}
+
+pub unsafe fn scraper_has_attr(ptr: HostPtr, attr_name: *const u8, attr_length: usize) -> bool {
+    let attr_name = string(attr_name, attr_length);
+    nodes(ptr).and_then(|nodes| nodes.attr(&attr_name)).is_some()
+}
+
+pub unsafe fn scraper_attributes(ptr: HostPtr) -> HostPtr {
+    let attributes = nodes(ptr)
+        .and_then(|nodes| {
+            let element = nodes.first_element()?.as_element()?;
+            let attributes = element.attributes.borrow();
+            Some(
+                attributes
+                    .map
+                    .iter()
+                    .map(|(key, attr)| (key.local.to_string(), Value::String(attr.value.clone())))
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+    super::insert(Value::Object(attributes))
+}
@@ -0,0 +1,329 @@
+use std::io::Read;
+
+use super::{bytes, string, with, write_bytes, HostPtr, Value};
+
+/// Mirrors `mochi::std::http::RequestMethod`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+impl From<i32> for Method {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Method::Post,
+            2 => Method::Put,
+            3 => Method::Patch,
+            4 => Method::Delete,
+            _ => Method::Get,
+        }
+    }
+}
+
+impl From<Method> for i32 {
+    fn from(value: Method) -> Self {
+        value as i32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestState {
+    pub method: Method,
+    pub url: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+    pub response: Option<Response>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub status: i32,
+    /// The URL after following redirects.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RequestState {
+    pub fn new(method: Method) -> Self {
+        Self {
+            method,
+            url: None,
+            headers: Vec::new(),
+            body: None,
+            response: None,
+        }
+    }
+
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn set_header(&mut self, key: String, value: String) {
+        self.headers.retain(|(k, _)| !k.eq_ignore_ascii_case(&key));
+        self.headers.push((key, value));
+    }
+}
+
+/// Performs the request over the network.
+pub(crate) fn fetch(request: &RequestState) -> Option<Response> {
+    let url = request.url.as_deref()?;
+    let mut call = ureq::request(request.method.as_str(), url);
+    for (key, value) in &request.headers {
+        call = call.set(key, value);
+    }
+    let result = match &request.body {
+        Some(body) => call.send_bytes(body),
+        None => call.call(),
+    };
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(_) => return None,
+    };
+
+    // `headers_names` repeats a name for each of its values.
+    let mut names = response.headers_names();
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    let headers = names
+        .into_iter()
+        .flat_map(|name| {
+            response
+                .all(&name)
+                .into_iter()
+                .map(|value| (name.clone(), value.to_owned()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let status = response.status() as i32;
+    let url = response.get_url().to_owned();
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body).ok()?;
+
+    Some(Response {
+        status,
+        url,
+        headers,
+        body,
+    })
+}
+
+fn with_request<R>(ptr: HostPtr, f: impl FnOnce(&mut RequestState) -> R) -> Option<R> {
+    with(|host| match host.get_mut(ptr) {
+        Some(Value::Request(request)) => Some(f(request)),
+        _ => None,
+    })
+}
+
+pub unsafe fn request_create(method: i32) -> i32 {
+    super::insert(Value::Request(RequestState::new(Method::from(method))))
+}
+
+/// Requests are only sent once; later calls are no-ops.
+pub unsafe fn request_send(ptr: i32) {
+    let request = match with_request(ptr, |request| request.clone()) {
+        Some(request) if request.response.is_none() => request,
+        _ => return,
+    };
+    let response = match with(|host| host.fixtures().cloned()) {
+        Some(fixtures) => fixtures.send(&request),
+        None => fetch(&request),
+    };
+    with_request(ptr, |request| request.response = response);
+}
+
+/// Sends every request that wasn't sent yet, each on its own thread.
+pub unsafe fn request_send_all(ptrs: *const i32, len: i32) {
+    let ptrs: &[i32] = if len <= 0 || ptrs.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptrs, len as usize)
+    };
+    let requests: Vec<(i32, RequestState)> = ptrs
+        .iter()
+        .filter_map(|&ptr| match with_request(ptr, |request| request.clone()) {
+            Some(request) if request.response.is_none() => Some((ptr, request)),
+            _ => None,
+        })
+        .collect();
+    let fixtures = with(|host| host.fixtures().cloned());
+
+    let responses: Vec<(i32, Option<Response>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = requests
+            .iter()
+            .map(|(ptr, request)| {
+                let fixtures = fixtures.as_ref();
+                scope.spawn(move || {
+                    let response = match fixtures {
+                        Some(fixtures) => fixtures.send(request),
+                        None => fetch(request),
+                    };
+                    (*ptr, response)
+                })
+            })
+            .collect();
+        // Joined here so a panic, like a missing fixture, keeps its message.
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    });
+    for (ptr, response) in responses {
+        with_request(ptr, |request| request.response = response);
+    }
+}
+
+/// Forgets the response, so the request can be sent again.
+pub unsafe fn request_reset(ptr: i32) {
+    with_request(ptr, |request| request.response = None);
+}
+
+pub unsafe fn request_close(ptr: i32) {
+    with(|host| {
+        if let Some(Value::Request(_)) = host.get(ptr) {
+            host.take(ptr);
+        }
+    })
+}
+
+pub unsafe fn request_set_url(ptr: i32, url_ptr: *const u8, url_len: i32) {
+    let url = string(url_ptr, url_len.max(0) as usize);
+    with_request(ptr, |request| request.url = Some(url));
+}
+
+pub unsafe fn request_set_header(
+    ptr: i32,
+    key_ptr: *const u8,
+    key_len: i32,
+    value_ptr: *const u8,
+    value_len: i32,
+) {
+    let key = string(key_ptr, key_len.max(0) as usize);
+    let value = string(value_ptr, value_len.max(0) as usize);
+    with_request(ptr, |request| request.set_header(key, value));
+}
+
+pub unsafe fn request_set_body(ptr: i32, data_ptr: *const u8, data_len: i32) {
+    let body = bytes(data_ptr, data_len.max(0) as usize).to_vec();
+    with_request(ptr, |request| request.body = Some(body));
+}
+
+pub unsafe fn request_set_method(ptr: i32, method: i32) {
+    with_request(ptr, |request| request.method = Method::from(method));
+}
+
+pub unsafe fn request_get_method(ptr: i32) -> i32 {
+    with_request(ptr, |request| request.method)
+        .unwrap_or(Method::Get)
+        .into()
+}
+
+pub unsafe fn request_get_url(ptr: i32) -> i32 {
+    let url = with_request(ptr, |request| request.url.clone()).flatten();
+    super::insert(Value::from(url))
+}
+
+pub unsafe fn request_get_header(ptr: i32, key_ptr: *const u8, key_len: i32) -> i32 {
+    let key = string(key_ptr, key_len.max(0) as usize);
+    let value = with_request(ptr, |request| request.header(&key).map(str::to_owned)).flatten();
+    super::insert(Value::from(value))
+}
+
+/// `-1` until the request was sent successfully.
+pub unsafe fn request_get_status_code(ptr: i32) -> i32 {
+    with_request(ptr, |request| request.response.as_ref().map(|r| r.status))
+        .flatten()
+        .unwrap_or(-1)
+}
+
+/// The URL the response came from, after following redirects.
+pub unsafe fn request_get_response_url(ptr: i32) -> i32 {
+    let url = with_request(ptr, |request| request.response.as_ref().map(|r| r.url.clone())).flatten();
+    super::insert(Value::from(url))
+}
+
+/// Response headers as an array of `[name, value]` pairs, in the order they
+/// were received. Repeated headers such as `Set-Cookie` get a pair each.
+pub unsafe fn request_get_response_headers(ptr: i32) -> i32 {
+    let headers = with_request(ptr, |request| {
+        request.response.as_ref().map(|response| {
+            response
+                .headers
+                .iter()
+                .map(|(key, value)| Value::Array(vec![Value::from(key.as_str()), Value::from(value.as_str())]))
+                .collect()
+        })
+    })
+    .flatten();
+    match headers {
+        Some(headers) => super::insert(Value::Array(headers)),
+        None => -1,
+    }
+}
+
+pub unsafe fn request_get_data_len(ptr: i32) -> i32 {
+    with_request(ptr, |request| request.response.as_ref().map(|r| r.body.len() as i32))
+        .flatten()
+        .unwrap_or(0)
+}
+
+pub unsafe fn request_get_data(ptr: i32, arr_ptr: *mut u8, len: i32) {
+    with_request(ptr, |request| {
+        if let Some(response) = &request.response {
+            write_bytes(arr_ptr, &response.body, len);
+        }
+    });
+}
+
+/// The cookies last saved with [set_cookies], or `-1`.
+pub unsafe fn get_cookies() -> i32 {
+    with(|host| match host.cookies().cloned() {
+        Some(cookies) => host.insert(cookies),
+        None => -1,
+    })
+}
+
+/// Saves a copy of the value behind `ptr`. An unknown pointer clears them.
+pub unsafe fn set_cookies(ptr: i32) {
+    with(|host| {
+        let cookies = host.get(ptr).cloned();
+        host.set_cookies(cookies);
+    })
+}
+
+/// The cached value stored for `key`, or `-1`.
+pub unsafe fn get_cache(key_ptr: *const u8, key_len: i32) -> i32 {
+    let key = string(key_ptr, key_len.max(0) as usize);
+    with(|host| match host.cache().get(&key).cloned() {
+        Some(value) => host.insert(value),
+        None => -1,
+    })
+}
+
+/// Stores a copy of the value behind `ptr`. An unknown pointer removes it.
+pub unsafe fn set_cache(key_ptr: *const u8, key_len: i32, ptr: i32) {
+    let key = string(key_ptr, key_len.max(0) as usize);
+    with(|host| match host.get(ptr).cloned() {
+        Some(value) => host.cache_mut().insert(key, value),
+        None => host.cache_mut().remove(&key),
+    });
+}
@@ -0,0 +1,26 @@
+use super::{bytes, HostPtr, Value};
+
+pub unsafe fn json_parse(bytes_ptr: *const u8, size: i32) -> HostPtr {
+    match serde_json::from_slice::<serde_json::Value>(bytes(bytes_ptr, size.max(0) as usize)) {
+        Ok(value) => super::insert(Value::from(value)),
+        Err(_) => -1,
+    }
+}
+
+/// Returns a string pointer, or `-1` if `ptr` is unknown. Html nodes are
+/// written as their outer html and crypto data as an array of bytes.
+pub unsafe fn json_stringify(ptr: HostPtr, pretty: bool) -> HostPtr {
+    let json = match super::value(ptr) {
+        Some(value) => value.to_json(),
+        None => return -1,
+    };
+    let string = if pretty {
+        serde_json::to_string_pretty(&json)
+    } else {
+        serde_json::to_string(&json)
+    };
+    match string {
+        Ok(string) => super::insert(Value::String(string)),
+        Err(_) => -1,
+    }
+}
@@ -0,0 +1,12 @@
+//! Settings picked with [Host::set_setting](super::Host::set_setting).
+
+use super::{string, with, HostPtr};
+
+/// A copy of the value picked for `id`, or `-1`.
+pub unsafe fn get(id_ptr: *const u8, id_len: i32) -> HostPtr {
+    let id = string(id_ptr, id_len.max(0) as usize);
+    with(|host| match host.settings().and_then(|settings| settings.get(&id)).cloned() {
+        Some(value) => host.insert(value),
+        None => -1,
+    })
+}
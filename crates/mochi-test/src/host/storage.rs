@@ -0,0 +1,77 @@
+//! Storage of the source set with [Host::set_source](super::Host::set_source).
+//! Values are copied in and out, so pointers never alias a stored value.
+
+use super::{bytes, string, with, write_bytes, HostPtr, Value};
+
+unsafe fn key(key_ptr: *const u8, key_len: i32) -> String {
+    string(key_ptr, key_len.max(0) as usize)
+}
+
+/// A copy of the value stored for `key`, or `-1`.
+pub unsafe fn get(key_ptr: *const u8, key_len: i32) -> HostPtr {
+    let key = key(key_ptr, key_len);
+    with(|host| match host.storage().and_then(|storage| storage.get(&key)).cloned() {
+        Some(value) => host.insert(value),
+        None => -1,
+    })
+}
+
+/// Stores a copy of the value behind `ptr`. An unknown pointer removes it.
+pub unsafe fn set(key_ptr: *const u8, key_len: i32, ptr: HostPtr) {
+    let key = key(key_ptr, key_len);
+    with(|host| match host.get(ptr).cloned() {
+        Some(value) => host.storage_mut().insert(key, value),
+        None => host.storage_mut().remove(&key),
+    });
+}
+
+fn data(value: &Value) -> Option<&[u8]> {
+    match value {
+        Value::Data(data) => Some(data),
+        Value::String(string) => Some(string.as_bytes()),
+        _ => None,
+    }
+}
+
+/// Length of the bytes stored for `key`, or `-1` if there are none.
+pub unsafe fn get_data_len(key_ptr: *const u8, key_len: i32) -> i32 {
+    let key = key(key_ptr, key_len);
+    with(|host| {
+        host.storage()
+            .and_then(|storage| storage.get(&key))
+            .and_then(data)
+            .map(|data| data.len() as i32)
+            .unwrap_or(-1)
+    })
+}
+
+pub unsafe fn get_data(key_ptr: *const u8, key_len: i32, buf_ptr: *mut u8, buf_len: i32) {
+    let key = key(key_ptr, key_len);
+    with(|host| {
+        if let Some(data) = host.storage().and_then(|storage| storage.get(&key)).and_then(data) {
+            write_bytes(buf_ptr, data, buf_len);
+        }
+    })
+}
+
+pub unsafe fn set_data(key_ptr: *const u8, key_len: i32, data_ptr: *const u8, data_len: i32) {
+    let key = key(key_ptr, key_len);
+    let data = bytes(data_ptr, data_len.max(0) as usize).to_vec();
+    with(|host| host.storage_mut().insert(key, Value::Data(data)));
+}
+
+pub unsafe fn remove(key_ptr: *const u8, key_len: i32) {
+    let key = key(key_ptr, key_len);
+    with(|host| host.storage_mut().remove(&key));
+}
+
+/// Every stored key, sorted.
+pub unsafe fn keys() -> HostPtr {
+    with(|host| {
+        let keys = host
+            .storage()
+            .map(|storage| storage.keys().cloned().map(Value::String).collect())
+            .unwrap_or_default();
+        host.insert(Value::Array(keys))
+    })
+}
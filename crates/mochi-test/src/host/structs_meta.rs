@@ -0,0 +1,225 @@
+//! The host's structs are emulated as objects with camelCase keys, so tests
+//! can inspect what a module returned with [Value::get].
+//!
+//! Array and object pointers passed to these constructors are forgotten by
+//! the module, so the host takes ownership of them.
+
+use super::{optional_string, with, HostPtr, Value};
+
+pub(crate) fn object(fields: Vec<(&str, Value)>) -> HostPtr {
+    let object = fields
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect();
+    super::insert(Value::Object(object))
+}
+
+pub(crate) fn owned(ptr: HostPtr) -> Value {
+    with(|host| host.take(ptr)).unwrap_or(Value::Null)
+}
+
+pub(crate) unsafe fn text(ptr: *const u8, len: i32) -> Value {
+    Value::from(optional_string(ptr, len))
+}
+
+pub(crate) fn optional_int(value: i32) -> Value {
+    if value < 0 {
+        Value::Null
+    } else {
+        Value::Int(value as i64)
+    }
+}
+
+pub unsafe fn create_search_filter_option(
+    option_id_ptr: *const u8,
+    option_id_len: i32,
+    name_ptr: *const u8,
+    name_len: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(option_id_ptr, option_id_len)),
+        ("displayName", text(name_ptr, name_len)),
+    ])
+}
+
+pub unsafe fn create_search_filter(
+    id_ptr: *const u8,
+    id_len: i32,
+    name_ptr: *const u8,
+    name_len: i32,
+    options_arr_ref: i32,
+    multiselect: bool,
+    required: bool,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("displayName", text(name_ptr, name_len)),
+        ("options", owned(options_arr_ref)),
+        ("multiselect", Value::Bool(multiselect)),
+        ("required", Value::Bool(required)),
+    ])
+}
+
+pub unsafe fn create_paging(
+    id_ptr: *const u8,
+    id_len: i32,
+    previous_page_ptr: *const u8,
+    previous_page_len: i32,
+    next_page_ptr: *const u8,
+    next_page_len: i32,
+    items_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("previousPage", text(previous_page_ptr, previous_page_len)),
+        ("nextPage", text(next_page_ptr, next_page_len)),
+        ("items", owned(items_ptr)),
+    ])
+}
+
+pub unsafe fn create_discover_listing(
+    title_ptr: *const u8,
+    title_len: i32,
+    lising_type: i32,
+    paging_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("title", text(title_ptr, title_len)),
+        ("type", Value::Int(lising_type as i64)),
+        ("paging", owned(paging_ptr)),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn create_playlist(
+    id_ptr: *const u8,
+    id_len: i32,
+    title_ptr: *const u8,
+    title_len: i32,
+    poster_image_ptr: *const u8,
+    poster_image_len: i32,
+    banner_image_ptr: *const u8,
+    banner_image_len: i32,
+    playlist_type: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("title", text(title_ptr, title_len)),
+        ("posterImage", text(poster_image_ptr, poster_image_len)),
+        ("bannerImage", text(banner_image_ptr, banner_image_len)),
+        ("type", Value::Int(playlist_type as i64)),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn create_playlist_details(
+    description_ptr: *const u8,
+    description_len: i32,
+    alternative_titles_ptr: i32,
+    alternative_posters_ptr: i32,
+    alternative_banners_ptr: i32,
+    genres_ptr: i32,
+    year_released: i32,
+    ratings: i32,
+    previews_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("contentDescription", text(description_ptr, description_len)),
+        ("alternativeTitles", owned(alternative_titles_ptr)),
+        ("alternativePosters", owned(alternative_posters_ptr)),
+        ("alternativeBanners", owned(alternative_banners_ptr)),
+        ("genres", owned(genres_ptr)),
+        ("yearReleased", optional_int(year_released)),
+        ("ratings", optional_int(ratings)),
+        ("previews", owned(previews_ptr)),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn create_playlist_preview(
+    title_ptr: *const u8,
+    title_len: i32,
+    description_ptr: *const u8,
+    description_len: i32,
+    thumbnail_ptr: *const u8,
+    thumbnail_len: i32,
+    link_ptr: *const u8,
+    link_len: i32,
+    preview_type: i32,
+) -> i32 {
+    object(vec![
+        ("title", text(title_ptr, title_len)),
+        ("description", text(description_ptr, description_len)),
+        ("thumbnail", text(thumbnail_ptr, thumbnail_len)),
+        ("link", text(link_ptr, link_len)),
+        ("type", Value::Int(preview_type as i64)),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn create_playlist_item(
+    id_ptr: *const u8,
+    id_len: i32,
+    title_ptr: *const u8,
+    title_len: i32,
+    description_ptr: *const u8,
+    description_len: i32,
+    thumbnail_ptr: *const u8,
+    thumbnail_len: i32,
+    number: f64,
+    timestamp_ptr: *const u8,
+    timestamp_len: i32,
+    tags_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("title", text(title_ptr, title_len)),
+        ("description", text(description_ptr, description_len)),
+        ("thumbnail", text(thumbnail_ptr, thumbnail_len)),
+        ("number", Value::Float(number)),
+        ("timestamp", text(timestamp_ptr, timestamp_len)),
+        ("tags", owned(tags_ptr)),
+    ])
+}
+
+pub unsafe fn create_playlist_items_response(contents_ptr: i32, all_groups_ptr: i32) -> i32 {
+    object(vec![
+        ("contents", owned(contents_ptr)),
+        ("allGroups", owned(all_groups_ptr)),
+    ])
+}
+
+pub unsafe fn create_playlist_group(
+    id: f64,
+    display_title_ptr: *const u8,
+    display_title_len: i32,
+) -> i32 {
+    object(vec![
+        ("id", Value::Float(id)),
+        ("displayTitle", text(display_title_ptr, display_title_len)),
+    ])
+}
+
+pub unsafe fn create_playlist_group_page(
+    id_ptr: *const u8,
+    id_len: i32,
+    display_name_ptr: *const u8,
+    display_name_len: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("displayName", text(display_name_ptr, display_name_len)),
+    ])
+}
+
+pub unsafe fn create_playlist_group_items(
+    group_id: f64,
+    pagings_ptr: i32,
+    all_pages_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("groupId", Value::Float(group_id)),
+        ("pagings", owned(pagings_ptr)),
+        ("allPages", owned(all_pages_ptr)),
+    ])
+}
@@ -0,0 +1,91 @@
+use super::structs_meta::{object, owned, text};
+use super::Value;
+
+pub unsafe fn create_episode_source(
+    id_ptr: *const u8,
+    id_len: i32,
+    display_name_ptr: *const u8,
+    display_name_len: i32,
+    description_ptr: *const u8,
+    description_len: i32,
+    servers_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("displayName", text(display_name_ptr, display_name_len)),
+        ("description", text(description_ptr, description_len)),
+        ("servers", owned(servers_ptr)),
+    ])
+}
+
+pub unsafe fn create_episode_server(
+    id_ptr: *const u8,
+    id_len: i32,
+    display_name_ptr: *const u8,
+    display_name_len: i32,
+    description_ptr: *const u8,
+    description_len: i32,
+) -> i32 {
+    object(vec![
+        ("id", text(id_ptr, id_len)),
+        ("displayName", text(display_name_ptr, display_name_len)),
+        ("description", text(description_ptr, description_len)),
+    ])
+}
+
+pub unsafe fn create_episode_server_response(
+    links_ptr: i32,
+    subtitles_ptr: i32,
+    skip_times_ptr: i32,
+    headers_ptr: i32,
+) -> i32 {
+    object(vec![
+        ("links", owned(links_ptr)),
+        ("subtitles", owned(subtitles_ptr)),
+        ("skipTimes", owned(skip_times_ptr)),
+        ("headers", owned(headers_ptr)),
+    ])
+}
+
+pub unsafe fn create_episode_server_link(
+    url_ptr: *const u8,
+    url_len: i32,
+    quality: i32,
+    format: i32,
+) -> i32 {
+    object(vec![
+        ("url", text(url_ptr, url_len)),
+        ("quality", Value::Int(quality as i64)),
+        ("format", Value::Int(format as i64)),
+    ])
+}
+
+pub unsafe fn create_episode_server_subtitle(
+    url_ptr: *const u8,
+    url_len: i32,
+    name_ptr: *const u8,
+    name_len: i32,
+    format: i32,
+    default: bool,
+    autoselect: bool,
+) -> i32 {
+    object(vec![
+        ("url", text(url_ptr, url_len)),
+        ("name", text(name_ptr, name_len)),
+        ("format", Value::Int(format as i64)),
+        ("default", Value::Bool(default)),
+        ("autoselect", Value::Bool(autoselect)),
+    ])
+}
+
+pub unsafe fn create_episode_server_skip_time(
+    start_time: f32,
+    end_time: f32,
+    skip_type: i32,
+) -> i32 {
+    object(vec![
+        ("startTime", Value::Float(start_time as f64)),
+        ("endTime", Value::Float(end_time as f64)),
+        ("type", Value::Int(skip_type as i64)),
+    ])
+}
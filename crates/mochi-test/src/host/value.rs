@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+
+use super::html::Nodes;
+use super::http::RequestState;
+
+/// A value owned by the host.
+///
+/// Objects and arrays own their children, so reading a child through
+/// `obj_get`/`array_get` hands out a copy under a new pointer.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+    Node(Nodes),
+    /// Raw bytes produced by the `crypto` module.
+    Data(Vec<u8>),
+    Request(RequestState),
+    /// An `Err` returned by an export, with the `mochi::std::error::ErrorKind`
+    /// of its `MochiError`, and fields such as the `status` and `url` of an
+    /// HTTP error and the `context` added to it.
+    Error { kind: i32, message: String, details: BTreeMap<String, Value> },
+}
+
+// Must match the order of `mochi::std::Kind`.
+pub(crate) const KIND_UNKNOWN: i32 = 0;
+pub(crate) const KIND_NULL: i32 = 1;
+pub(crate) const KIND_OBJECT: i32 = 2;
+pub(crate) const KIND_ARRAY: i32 = 3;
+pub(crate) const KIND_STRING: i32 = 4;
+pub(crate) const KIND_NUMBER: i32 = 5;
+pub(crate) const KIND_BOOL: i32 = 6;
+pub(crate) const KIND_NODE: i32 = 7;
+
+impl Value {
+    pub(crate) fn kind(&self) -> i32 {
+        match self {
+            Value::Null => KIND_NULL,
+            Value::Bool(_) => KIND_BOOL,
+            Value::Int(_) | Value::Float(_) => KIND_NUMBER,
+            Value::String(_) => KIND_STRING,
+            Value::Array(_) => KIND_ARRAY,
+            Value::Object(_) => KIND_OBJECT,
+            Value::Node(_) => KIND_NODE,
+            Value::Data(_) | Value::Request(_) | Value::Error { .. } => KIND_UNKNOWN,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Numeric value, coercing bools and numeric strings like the app does.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Value::String(value) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            Value::String(value) => value
+                .trim()
+                .parse()
+                .ok()
+                .or_else(|| self.as_f64().map(|v| v as i64)),
+            _ => self.as_f64().map(|v| v as i64),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            Value::Int(value) => Some(*value != 0),
+            Value::Float(value) => Some(*value != 0.0),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Object(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_data(&self) -> Option<&[u8]> {
+        match self {
+            Value::Data(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The kind and message of an error.
+    pub fn as_error(&self) -> Option<(i32, &str)> {
+        match self {
+            Value::Error { kind, message, .. } => Some((*kind, message)),
+            _ => None,
+        }
+    }
+
+    /// The fields an error was created with.
+    pub fn error_details(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Error { details, .. } => Some(details),
+            _ => None,
+        }
+    }
+
+    /// Get an object's field, returning `None` for any other value.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object().and_then(|object| object.get(key))
+    }
+
+    /// A JSON view of this value. Nodes are serialized as their outer HTML,
+    /// errors as `{"error": kind, "message": message}` along with their
+    /// details, and values with no JSON equivalent become `null`.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Value::Null | Value::Request(_) => Json::Null,
+            Value::Error { kind, message, details } => {
+                let mut error: serde_json::Map<String, Json> = details
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect();
+                error.insert("error".into(), Json::from(*kind));
+                error.insert("message".into(), Json::String(message.clone()));
+                Json::Object(error)
+            }
+            Value::Bool(value) => Json::Bool(*value),
+            Value::Int(value) => Json::from(*value),
+            Value::Float(value) => serde_json::Number::from_f64(*value)
+                .map(Json::Number)
+                .unwrap_or(Json::Null),
+            Value::String(value) => Json::String(value.clone()),
+            Value::Array(values) => Json::Array(values.iter().map(Value::to_json).collect()),
+            Value::Object(values) => Json::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+            Value::Node(nodes) => Json::String(nodes.outer_html()),
+            Value::Data(bytes) => Json::Array(bytes.iter().map(|b| Json::from(*b)).collect()),
+        }
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        use serde_json::Value as Json;
+        match value {
+            Json::Null => Value::Null,
+            Json::Bool(value) => Value::Bool(value),
+            Json::Number(number) => match number.as_i64() {
+                Some(value) => Value::Int(value),
+                None => Value::Float(number.as_f64().unwrap_or_default()),
+            },
+            Json::String(value) => Value::String(value),
+            Json::Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+            Json::Object(values) => Value::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<Option<String>> for Value {
+    fn from(value: Option<String>) -> Self {
+        value.map(Value::String).unwrap_or(Value::Null)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
@@ -1 +1,13 @@
-pub use mochi_test_macro::*;
\ No newline at end of file
+//! Native emulation of the Mochi host.
+//!
+//! `mochi-rs` only resolves its host imports inside the Mochi app. On
+//! non-wasm targets it links them against [host] instead, so the same
+//! `PtrRef`, `Node` and `Request` code can run under `cargo test`.
+//!
+//! HTTP requests can be served from recorded [fixtures] to run offline.
+
+pub mod fixtures;
+pub mod host;
+pub mod server;
+
+pub use mochi_test_macro::mochi_test;
@@ -0,0 +1,231 @@
+//! A minimal local HTTP server to record [fixtures](crate::fixtures) against,
+//! instead of the site a source scrapes.
+//!
+//! ```ignore
+//! let server = Server::start()?;
+//! server.route(Route::get("/search?q=naruto").body(include_str!("search.html")));
+//!
+//! Fixtures::record("tests/fixtures/search")
+//!     .redirect("https://example.com", server.url(""))
+//!     .install();
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A canned response, served for requests matching its method and path.
+#[derive(Debug, Clone)]
+pub struct Route {
+    method: String,
+    path: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    remaining: Option<usize>,
+}
+
+impl Route {
+    /// `path` is matched exactly against the request target, query included.
+    pub fn new<A: Into<String>, B: Into<String>>(method: A, path: B) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            status: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+            remaining: None,
+        }
+    }
+
+    pub fn get<T: Into<String>>(path: T) -> Self {
+        Self::new("GET", path)
+    }
+
+    pub fn post<T: Into<String>>(path: T) -> Self {
+        Self::new("POST", path)
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn header<A: Into<String>, B: Into<String>>(mut self, key: A, value: B) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn body<T: Into<Vec<u8>>>(mut self, body: T) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Only serve the route `count` times, after which earlier routes match
+    /// again. Useful to fail a request before it succeeds.
+    pub fn times(mut self, count: usize) -> Self {
+        self.remaining = Some(count);
+        self
+    }
+}
+
+/// A request the server received, to assert on what a source sent.
+#[derive(Debug, Clone)]
+pub struct Received {
+    pub method: String,
+    pub path: String,
+    /// Headers in the order they were received, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Received {
+    /// The first value of a header, ignoring case.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Serves its routes on `127.0.0.1` from a background thread, answering
+/// `404` to anything else, until dropped.
+pub struct Server {
+    addr: SocketAddr,
+    routes: Arc<Mutex<Vec<Route>>>,
+    received: Arc<Mutex<Vec<Received>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Server {
+    /// Bind to a free port and start serving.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let routes = Arc::new(Mutex::new(Vec::<Route>::new()));
+        let received = Arc::new(Mutex::new(Vec::<Received>::new()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let (thread_routes, thread_received, thread_running) = (routes.clone(), received.clone(), running.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !thread_running.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = handle(stream, &thread_routes, &thread_received);
+                }
+            }
+        });
+
+        Ok(Self { addr, routes, received, running })
+    }
+
+    /// Add a route. Later routes take precedence.
+    pub fn route(&self, route: Route) -> &Self {
+        self.routes.lock().unwrap().insert(0, route);
+        self
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Absolute URL of `path` on this server.
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn handle(stream: TcpStream, routes: &Mutex<Vec<Route>>, received: &Mutex<Vec<Received>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+        _ => return Ok(()),
+    };
+
+    let mut content_length = 0;
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim().to_owned());
+            if key == "content-length" {
+                content_length = value.parse().unwrap_or(0);
+            }
+            headers.push((key, value));
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    received.lock().unwrap().push(Received {
+        method: method.clone(),
+        path: path.clone(),
+        headers,
+        body,
+    });
+
+    let route = routes
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .find(|route| {
+            route.method.eq_ignore_ascii_case(&method) && route.path == path && route.remaining != Some(0)
+        })
+        .map(|route| {
+            if let Some(remaining) = &mut route.remaining {
+                *remaining -= 1;
+            }
+            route.clone()
+        })
+        .unwrap_or_else(|| Route::new(method, path).status(404));
+
+    let mut stream = stream;
+    write!(stream, "HTTP/1.1 {} {}\r\n", route.status, reason(route.status))?;
+    for (key, value) in &route.headers {
+        write!(stream, "{}: {}\r\n", key, value)?;
+    }
+    write!(stream, "Content-Length: {}\r\nConnection: close\r\n\r\n", route.body.len())?;
+    stream.write_all(&route.body)?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
@@ -0,0 +1,46 @@
+//! Support for the arguments `#[mochi_bind]` exports take by value rather
+//! than as host pointers.
+//!
+//! Strings are written by the host into a buffer it gets from
+//! `mochi_alloc(len)`, and handed over as `(ptr, len)`. The export takes
+//! ownership of the buffer, so the host doesn't free it. A buffer the host
+//! ends up not passing is freed with `mochi_dealloc(ptr, len)`.
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Allocates `len` bytes for a string argument.
+#[cfg_attr(target_arch = "wasm32", export_name = "mochi_alloc")]
+pub extern "C" fn mochi_alloc(len: i32) -> i32 {
+    let mut buf = Vec::<u8>::with_capacity(len.max(0) as usize);
+    let ptr = buf.as_mut_ptr();
+    core::mem::forget(buf);
+    ptr as usize as i32
+}
+
+/// Frees a buffer from [mochi_alloc] that wasn't passed to an export.
+///
+/// # Safety
+/// `ptr` and `len` must come from the same call to [mochi_alloc].
+#[cfg_attr(target_arch = "wasm32", export_name = "mochi_dealloc")]
+pub unsafe extern "C" fn mochi_dealloc(ptr: i32, len: i32) {
+    drop(Vec::from_raw_parts(ptr as usize as *mut u8, 0, len.max(0) as usize));
+}
+
+/// Takes ownership of a string argument, where a negative `len` is `None`.
+/// Invalid UTF-8 is replaced.
+///
+/// # Safety
+/// `ptr` and `len` must come from the same call to [mochi_alloc], with
+/// `len` bytes written.
+pub unsafe fn take_string(ptr: i32, len: i32) -> Option<String> {
+    if len < 0 {
+        return None;
+    }
+    let buf = Vec::from_raw_parts(ptr as usize as *mut u8, len as usize, len as usize);
+    Some(match String::from_utf8(buf) {
+        Ok(string) => string,
+        Err(error) => String::from_utf8_lossy(error.as_bytes()).into_owned(),
+    })
+}
@@ -1,39 +1,46 @@
 extern crate alloc;
 
-use alloc::format;
 use alloc::string::ToString;
 use alloc::vec;
 use alloc::vec::Vec;
 use crate::imports::crypto::Crypto;
-use crate::imports::error::Result;
+use crate::imports::error::{Context, MochiError, Result};
 use crate::imports::http::RequestMethod;
+use crate::imports::http::Cache;
 use crate::imports::http::Request;
+use crate::imports::url::Url;
 use crate::structs::video::PlaylistEpisodeServerLink;
 use crate::structs::video::PlaylistEpisodeServerResponse;
 use crate::structs::video::PlaylistEpisodeServerSkipTime;
 use crate::structs::video::PlaylistEpisodeServerSubtitle;
 
-static FALLBACK_KEY: &'static str = "c1d17096f2ca11b7";
-static HOST: &'static str = "https://rapid-cloud.co";
+static FALLBACK_KEY: &str = "c1d17096f2ca11b7";
+static HOST: &str = "https://rapid-cloud.co";
+/// Seconds the decryption key is cached for.
+const KEY_TTL: u32 = 10 * 60;
 
 pub struct RapidCloud {}
 
 impl RapidCloud {
     pub fn extract(video_url: &str) -> Result<PlaylistEpisodeServerResponse> {
         // Example: https://rapid-cloud.co/embed-6/fEjafeaEF?k=1
-        let id = video_url
-            .split("/")
-            .last()
-            .and_then(|v| v.split("?").nth(0))
-            .expect("ID could not be found for rapidcloud link.");
+        let id = Url::parse(video_url)?
+            .path_segments()
+            .pop()
+            .ok_or_else(|| MochiError::Extractor("rapidcloud link has no id".to_string()))?;
 
-        let url = format!("{}/ajax/embed-6/getSources?id={}", HOST, id);
+        let url = Url::parse(HOST)?
+            .join("/ajax/embed-6/getSources")?
+            .query("id", id);
 
         let json = Request::new(
             &url, 
             RequestMethod::Get
         )
         .header("X-Requested-With", "XMLHttpRequest")
+        .send()
+        .error_for_status()
+        .context("while fetching rapidcloud sources")?
         .json()?
         .as_object()?;
 
@@ -47,17 +54,23 @@ impl RapidCloud {
         let mut links: Vec<PlaylistEpisodeServerLink> = vec![];
 
         if encrypted {
+            // The key rotates, so it's only kept briefly and never from the
+            // host's own cache.
             let encrypted_key = Request::new(
-                // TODO: Add param at the end so we don't cache request
                 "https://raw.githubusercontent.com/enimax-anime/key/e6/key.txt", 
                 RequestMethod::Get
             )
+            .header("Cache-Control", "no-cache")
+            .cache(Cache::new(KEY_TTL))
             .string()
             .unwrap_or(FALLBACK_KEY.to_string())
             .as_bytes()
             .to_vec();
 
             let encrypted_sources_bytes = Crypto::base64_parse(&sources_encrypted);
+            if encrypted_sources_bytes.len() < 16 {
+                return Err(MochiError::Crypto("rapidcloud sources are too short to be encrypted".to_string()));
+            }
             let encrypted_salt = encrypted_sources_bytes[8..16].to_vec();
 
             let mut encrypted_key_and_salt = encrypted_key;
@@ -108,15 +121,15 @@ impl RapidCloud {
             let kind = track.get("kind")
                 .as_string()
                 .unwrap_or_default();
-            let default = track.get("default").as_bool();
+            let default = track.get("default").as_bool().unwrap_or(false);
             if kind.contains("captions") {
                 subtitles.push(
                     PlaylistEpisodeServerSubtitle { 
                         url: file, 
                         name: label, 
                         format: crate::structs::video::PlaylistEpisodeServerSubtitleFormat::VTT,
-                        default: default.unwrap_or(false),
-                        autoselect: default.unwrap_or(false),
+                        default,
+                        autoselect: default,
                     }
                 )
             }
@@ -1,11 +1,13 @@
 extern crate alloc;
 
-use alloc::{format, vec, vec::Vec, string::String};
+use alloc::{vec, vec::Vec, string::{String, ToString}};
 use crate::structs::video::{PlaylistEpisodeServerResponse, PlaylistEpisodeServerSubtitle, PlaylistEpisodeServerSubtitleFormat, PlaylistEpisodeServerLink, PlaylistEpisodeServerHeader};
-use crate::imports::{error::Result, http::{Request, RequestMethod}, crypto::Crypto};
+use crate::imports::{error::{Context, MochiError, Result}, http::{Cache, Request, RequestMethod}, crypto::Crypto, url::Url};
 
-static HOST: &'static str = "https://dokicloud.one";
-static HOST2: &'static str = "https://rabbitstream.net";
+static HOST: &str = "https://dokicloud.one";
+static HOST2: &str = "https://rabbitstream.net";
+/// Seconds the decryption key is cached for.
+const KEY_TTL: u32 = 10 * 60;
 
 pub struct VidCloud {}
 
@@ -14,22 +16,24 @@ impl VidCloud {
         video_url: &str,
         is_variant: bool
     ) -> Result<PlaylistEpisodeServerResponse> {
-        let id = video_url
-            .split("/")
-            .last()
-            .expect("video url does not have id")
-            .split_once("?")
-            .map(|o| o.0)
-            .expect("video url does not have id");
+        let id = Url::parse(video_url)?
+            .path_segments()
+            .pop()
+            .ok_or_else(|| MochiError::Extractor("vidcloud link has no id".to_string()))?;
 
         let host_url = if is_variant { HOST2 } else { HOST };
 
         let response = Request::new(
-            &format!("{}/ajax/embed-4/getSources?id={}", host_url, id), 
+            Url::parse(host_url)?
+                .join("/ajax/embed-4/getSources")?
+                .query("id", id),
             RequestMethod::Get
         )
         .header("X-Requested-With", "XMLHttpRequest",)
         .header("Referer", video_url)
+        .send()
+        .error_for_status()
+        .context("while fetching vidcloud sources")?
         .json()?
         .as_object()?;
 
@@ -44,16 +48,19 @@ impl VidCloud {
                 "https://raw.githubusercontent.com/enimax-anime/key/e4/key.txt", 
                 RequestMethod::Get
             )
+            .header("Cache-Control", "no-cache")
+            .cache(Cache::new(KEY_TTL))
             .json()
-            .expect("json array of nested ints")
-            .as_array()?
+            .context("vidcloud key is not json")?
+            .as_array()
+            .context("vidcloud key is not an array")?
             .map(|e| {
                 e.as_array()
-                    .expect("nested array of ints")
-                    .map(|n| n.as_int().expect("int value"))
+                    .context("vidcloud key has a range that is not an array")?
+                    .map(|n| n.as_int().context("vidcloud key has a bound that is not an int"))
                     .collect()
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
             let mut key: Vec<u8> = vec![];
 
@@ -64,7 +71,11 @@ impl VidCloud {
                 .to_vec();
 
             for i in encrypted_key {
-                for j in i[0]..i[1] {
+                let (start, end) = match i[..] {
+                    [start, end] if 0 <= start && start <= end && (end as usize) <= encrypted_sources.len() => (start, end),
+                    _ => return Err(MochiError::Extractor("vidcloud key has an invalid range".to_string())),
+                };
+                for j in start..end {
                     key.push(encrypted_sources[j as usize]);
                     encrypted_sources[j as usize] = b' ';
                 }
@@ -73,6 +84,9 @@ impl VidCloud {
             encrypted_sources.retain(|x| x != &b' ');
 
             let encrypted_bytes = Crypto::base64_parse(&String::from_utf8(encrypted_sources).unwrap_or_default());
+            if encrypted_bytes.len() < 16 {
+                return Err(MochiError::Crypto("vidcloud sources are too short to be encrypted".to_string()));
+            }
             let encrypted_salt = encrypted_bytes[8..16].to_vec();
 
             let mut encrypted_key_and_salt = key;
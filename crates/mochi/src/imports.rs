@@ -4,4 +4,11 @@ pub mod crypto;
 pub mod error;
 pub mod html;
 pub mod http;
-pub mod json;
\ No newline at end of file
+pub mod json;
+pub mod log;
+mod path;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod settings;
+pub mod storage;
+pub mod url;
\ No newline at end of file
@@ -1,16 +1,19 @@
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use super::html::Node;
 
 use super::error::{Result, MochiError, PtrCastError};
 
+#[cfg(not(feature = "test-host"))]
 type MutRawBufPtr = *mut u8;
+#[cfg(not(feature = "test-host"))]
 type RawBufPtr = *const u8;
 pub(crate) type HostPtr = i32;
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "core")]
 extern "C" {
     pub(crate) fn copy(ptr: HostPtr) -> HostPtr;
@@ -18,11 +21,12 @@ extern "C" {
 
     fn create_array() -> HostPtr;
     fn create_obj() -> HostPtr;
+    fn create_null() -> HostPtr;
     fn create_string(buf_raw_ptr: RawBufPtr, buf_len: i32) -> HostPtr;
     fn create_bool(value: bool) -> HostPtr;
     fn create_float(value: f64) -> HostPtr;
     fn create_int(value: i64) -> HostPtr;
-    fn create_error() -> HostPtr;
+    fn create_error_with(kind: i32, msg_raw_ptr: RawBufPtr, msg_len: i32, details: HostPtr) -> HostPtr;
 
     pub(crate) fn ptr_kind(ptr: HostPtr) -> Kind;
 
@@ -46,17 +50,63 @@ extern "C" {
     fn array_remove(ptr: HostPtr, idx: i32);
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::core::*;
+#[cfg(feature = "test-host")]
+pub(crate) use mochi_test::host::core::{copy, destroy};
+
+#[cfg(feature = "test-host")]
+pub(crate) unsafe fn ptr_kind(ptr: HostPtr) -> Kind {
+    match mochi_test::host::core::ptr_kind(ptr) {
+        1 => Kind::Null,
+        2 => Kind::Object,
+        3 => Kind::Array,
+        4 => Kind::String,
+        5 => Kind::Number,
+        6 => Kind::Bool,
+        7 => Kind::Node,
+        _ => Kind::Unknown,
+    }
+}
+
 /// Prints a message to the Aidoku logs.
 pub fn print<T: AsRef<str>>(string: T) {
     let string = string.as_ref();
+    #[cfg(not(feature = "test-host"))]
     extern "C" {
         fn print(string: *const u8, size: usize);
     }
+    #[cfg(feature = "test-host")]
+    use mochi_test::host::core::print;
     unsafe {
         print(string.as_ptr(), string.len());
     }
 }
 
+/// Blocks for `millis` milliseconds.
+pub fn sleep(millis: u32) {
+    #[cfg(not(feature = "test-host"))]
+    extern "C" {
+        fn sleep(millis: i32);
+    }
+    #[cfg(feature = "test-host")]
+    use mochi_test::host::core::sleep;
+    unsafe {
+        sleep(i32::try_from(millis).unwrap_or(i32::MAX));
+    }
+}
+
+/// Milliseconds since the Unix epoch, according to the host.
+pub fn now() -> f64 {
+    #[cfg(not(feature = "test-host"))]
+    extern "C" {
+        fn now() -> f64;
+    }
+    #[cfg(feature = "test-host")]
+    use mochi_test::host::core::now;
+    unsafe { now() }
+}
+
 #[repr(C)]
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Kind {
@@ -89,6 +139,20 @@ impl PtrRef {
         PtrRef(ptr)
     }
 
+    /// A pointer the host doesn't know about, which it reads as
+    /// [Kind::Null], like the ones it returns for missing values.
+    #[inline]
+    pub fn null() -> Self {
+        PtrRef(-1)
+    }
+
+    /// A null value held by the host, to store in objects and arrays, which
+    /// only promise to hold values the host created.
+    #[inline]
+    pub fn null_value() -> Self {
+        PtrRef(unsafe { create_null() })
+    }
+
     #[inline]
     pub fn pointer(&self) -> HostPtr {
         self.0
@@ -199,11 +263,44 @@ impl PtrRef {
         }
     }
 
+    /// The value at `key`, or a null pointer if this isn't an object.
+    pub(crate) fn child(&self, key: &str) -> PtrRef {
+        match self.kind() {
+            Kind::Object => PtrRef::new(unsafe { obj_get(self.0, key.as_ptr(), key.len()) }),
+            _ => PtrRef::null(),
+        }
+    }
+
+    /// The item at `index`, or a null pointer if this isn't an array.
+    pub(crate) fn item(&self, index: i32) -> PtrRef {
+        match self.kind() {
+            Kind::Array => PtrRef::new(unsafe { array_get(self.0, index) }),
+            _ => PtrRef::null(),
+        }
+    }
+
+    /// Length of an array, `0` for other values.
+    pub(crate) fn items_len(&self) -> i32 {
+        match self.kind() {
+            Kind::Array => unsafe { array_len(self.0) },
+            _ => 0,
+        }
+    }
+
+    /// Values of an object or items of an array.
+    pub(crate) fn children(&self) -> ArrayRef {
+        match self.kind() {
+            Kind::Object => ArrayRef::from(PtrRef::new(unsafe { obj_values(self.0) })),
+            Kind::Array => ArrayRef::from(self.clone()),
+            _ => ArrayRef::new(),
+        }
+    }
+
     /// Cast the ValueRef to a [Node](crate::html::Node).
     pub fn as_node(&self) -> Result<Node> {
         match self.kind() {
             Kind::Node => {
-                Ok(unsafe { Node::from(self.0) })
+                Ok(unsafe { Node::from(copy(self.0)) })
             },
             Kind::Null => {
                 Err(MochiError::from(PtrCastError::NullPointer))
@@ -235,7 +332,7 @@ impl From<i32> for PtrRef {
 
 impl From<i64> for PtrRef {
     fn from(value: i64) -> Self {
-        PtrRef(unsafe { create_int(value as i64) })
+        PtrRef(unsafe { create_int(value) })
     }
 }
 
@@ -247,7 +344,7 @@ impl From<f32> for PtrRef {
 
 impl From<f64> for PtrRef {
     fn from(value: f64) -> Self {
-        PtrRef(unsafe { create_float(value as f64) })
+        PtrRef(unsafe { create_float(value) })
     }
 }
 
@@ -273,8 +370,55 @@ impl<T> From<Result<T>> for PtrRef where PtrRef: From<T> {
     fn from(value: Result<T>) -> Self {
         match value {
             Result::Ok(val) => val.into(),
-            Result::Err(_) => PtrRef(unsafe { create_error() }),
+            Result::Err(error) => error.into(),
+        }
+    }
+}
+
+/// An error value with the error's kind and message, and details the host
+/// can read as fields:
+/// - `context`, the [context](super::error::Context) added to the error,
+///   outermost first
+/// - `cause`, the message of the error without context
+/// - `status` and `url`, if the cause is [MochiError::Http]
+impl From<MochiError> for PtrRef {
+    fn from(error: MochiError) -> Self {
+        let mut details = ObjectRef::new();
+        let mut context = ArrayRef::new();
+        let mut cause = &error;
+        while let MochiError::Context { context: message, source } = cause {
+            context.insert(message.as_str().into());
+            cause = source;
         }
+        details.set("context", context.into());
+        details.set("cause", cause.to_string().into());
+        if let MochiError::Http { status, url } = cause {
+            details.set("status", (*status).into());
+            details.set("url", url.as_str().into());
+        }
+
+        let message = error.to_string();
+        PtrRef(unsafe {
+            create_error_with(error.kind() as i32, message.as_ptr(), message.len() as i32, details.ptr())
+        })
+    }
+}
+
+impl<T: Into<PtrRef>> From<Vec<T>> for PtrRef {
+    fn from(value: Vec<T>) -> Self {
+        ArrayRef::from(value).into()
+    }
+}
+
+impl From<ObjectRef> for PtrRef {
+    fn from(value: ObjectRef) -> Self {
+        value.0
+    }
+}
+
+impl From<ArrayRef> for PtrRef {
+    fn from(value: ArrayRef) -> Self {
+        value.0
     }
 }
 
@@ -284,6 +428,45 @@ impl Into<String> for PtrRef {
     }
 }
 
+impl Into<i32> for PtrRef {
+    fn into(self) -> i32 {
+        self.as_int().unwrap_or_default() as i32
+    }
+}
+
+impl Into<i64> for PtrRef {
+    fn into(self) -> i64 {
+        self.as_int().unwrap_or_default()
+    }
+}
+
+impl Into<f32> for PtrRef {
+    fn into(self) -> f32 {
+        self.as_float().unwrap_or_default() as f32
+    }
+}
+
+impl Into<f64> for PtrRef {
+    fn into(self) -> f64 {
+        self.as_float().unwrap_or_default()
+    }
+}
+
+impl Into<bool> for PtrRef {
+    fn into(self) -> bool {
+        self.as_bool().unwrap_or_default()
+    }
+}
+
+/// Items that aren't arrays are read as an empty `Vec`.
+impl<T> Into<Vec<T>> for PtrRef where PtrRef: Into<T> {
+    fn into(self) -> Vec<T> {
+        self.as_array()
+            .map(|array| array.map(|item| item.into()).collect())
+            .unwrap_or_default()
+    }
+}
+
 /// A key-value object, typically shown as a dictionary
 /// 
 /// 
@@ -358,7 +541,7 @@ pub struct ArrayRef(
 impl ArrayRef {
     pub fn new() -> Self {
         let pid = unsafe { create_array() };
-        Self(PtrRef::new(pid), 0, 0)
+        Self(PtrRef::new(pid), 0, -1)
     }
 
     #[inline]
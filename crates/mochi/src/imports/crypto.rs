@@ -4,32 +4,33 @@ use alloc::{string::String, vec::Vec};
 
 use super::core::PtrRef;
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "crypto")]
 // #[link(name = "swift-bindings", kind = "static")]
 extern "C" {
     fn crypto_get_data_len(host_ptr: i32) -> i32;
-    fn crypto_get_data(host_ptr: i32, buf_ptr: i32, buf_len: i32);
+    fn crypto_get_data(host_ptr: i32, buf_ptr: *mut u8, buf_len: i32);
 
     fn crypto_base64_parse(
-        value_ptr: i32,
+        value_ptr: *const u8,
         value_len: i32
     ) -> i32;
 
     fn crypto_base64_string(
-        bytes_ptr: i32,
+        bytes_ptr: *const u8,
         bytes_len: i32
     ) -> i32;
 
     fn crypto_utf8_parse(
-        value_ptr: i32, 
+        value_ptr: *const u8, 
         value_len: i32
     ) -> i32;
 
     fn crypto_pbkdf2(
         hash_algorithm: i32,
-        password_ptr: i32,
+        password_ptr: *const u8,
         password_len: i32,
-        salt_ptr: i32,
+        salt_ptr: *const u8,
         salt_len: i32,
         rounds: i32,
         key_len: i32
@@ -38,29 +39,32 @@ extern "C" {
     fn crypto_generate_random_bytes(count: i32) -> i32;
 
     fn crypto_aes_encrypt(
-        msg_ptr: i32,
+        msg_ptr: *const u8,
         msg_len: i32,
-        key_ptr: i32,
+        key_ptr: *const u8,
         key_len: i32,
-        iv_ptr: i32,
+        iv_ptr: *const u8,
         iv_len: i32
     ) -> i32;
 
     fn crypto_aes_decrypt(
-        encrypted_msg_ptr: i32,
+        encrypted_msg_ptr: *const u8,
         encrypted_msg_len: i32,
-        key_ptr: i32,
+        key_ptr: *const u8,
         key_len: i32,
-        iv_ptr: i32,
+        iv_ptr: *const u8,
         iv_len: i32
     ) -> i32;
 
     fn crypto_md5_hash(
-        input_ptr: i32,
+        input_ptr: *const u8,
         input_len: i32
     ) -> i32;
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::crypto::*;
+
 
 #[repr(C)]
 pub enum CryptoPBKDFAlgorithm {
@@ -82,7 +86,7 @@ impl Crypto {
     pub fn utf8_parse(value: &str) -> String {
         let host_data_ptr = unsafe {
             crypto_utf8_parse(
-                value.as_ptr() as i32, 
+                value.as_ptr(), 
                 value.len() as i32
             )
         };
@@ -93,7 +97,7 @@ impl Crypto {
     pub fn base64_parse(value: &str) -> Vec<u8> {
         let host_data_ptr = unsafe {
             crypto_base64_parse(
-                value.as_ptr() as i32, 
+                value.as_ptr(), 
                 value.len() as i32
             )
         };
@@ -103,7 +107,7 @@ impl Crypto {
     pub fn base64_string(bytes: &[u8]) -> String {
         let host_string_ptr = unsafe {
             crypto_base64_string(
-                bytes.as_ptr() as i32, 
+                bytes.as_ptr(), 
                 bytes.len() as i32
             )
         };
@@ -121,11 +125,11 @@ impl Crypto {
     ) -> Vec<u8> {
         let data_ptr = unsafe {
             crypto_aes_encrypt(
-                msg.as_ptr() as i32, 
+                msg.as_ptr(), 
                 msg.len() as i32, 
-                key.as_ptr() as i32, 
+                key.as_ptr(), 
                 key.len() as i32, 
-                iv.as_ptr() as i32, 
+                iv.as_ptr(), 
                 iv.len() as i32
             )
         };
@@ -139,11 +143,11 @@ impl Crypto {
     ) -> Vec<u8> {
         let data_ptr = unsafe {
             crypto_aes_decrypt(
-                encrypted_msg.as_ptr() as i32, 
+                encrypted_msg.as_ptr(), 
                 encrypted_msg.len() as i32, 
-                key.as_ptr() as i32, 
+                key.as_ptr(), 
                 key.len() as i32, 
-                iv.as_ptr() as i32, 
+                iv.as_ptr(), 
                 iv.len() as i32
             )
         };
@@ -158,7 +162,7 @@ impl Crypto {
     ) -> Vec<u8> {
         let host_ptr = unsafe {
             crypto_md5_hash(
-                input.as_ptr() as i32, 
+                input.as_ptr(), 
                 input.len() as i32
             )
         };
@@ -177,9 +181,9 @@ impl Crypto {
         let host_ptr = unsafe {
             crypto_pbkdf2(
                 hash_algorithm as i32, 
-                password.as_ptr() as i32, 
+                password.as_ptr(), 
                 password.len() as i32, 
-                salt.as_ptr() as i32, 
+                salt.as_ptr(), 
                 salt.len() as i32, 
                 rounds, 
                 key_len
@@ -203,7 +207,7 @@ fn data_to_vec(host_data_ptr: i32) -> Vec<u8> {
         unsafe {
             crypto_get_data(
                 host_data_ptr, 
-                buf.as_mut_ptr() as i32, 
+                buf.as_mut_ptr(), 
                 data_size
             );
             buf.set_len(data_size as usize);
@@ -1,17 +1,147 @@
-use core::num::ParseIntError;
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::{FromUtf8Error, String, ToString};
+use core::fmt;
+use core::num::{ParseFloatError, ParseIntError};
+use core::str::Utf8Error;
 
 pub type Result<T> = core::result::Result<T, MochiError>;
 
-#[repr(C)]
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum MochiError {
     PtrCast(PtrCastError),
     Node(NodeError),
     JsonParseError,
+    JsonStringifyError,
+    UrlParseError,
+    /// A response with a `4xx` or `5xx` status, or `-1` if it wasn't sent.
+    Http { status: i32, url: String },
+    /// A number or string that couldn't be parsed.
+    Parse(String),
+    /// Data that couldn't be decrypted or decoded.
+    Crypto(String),
+    /// A video host whose page or sources changed.
+    Extractor(String),
+    Custom(String),
+    /// An error with what was being done when it happened, added with
+    /// [Context::context].
+    Context { context: String, source: Box<MochiError> },
     Unimplemented,
     Unknown
 }
 
+/// The variant of a [MochiError], as the host receives it with the error's
+/// message when an export returns `Err`.
+#[repr(i32)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ErrorKind {
+    Unknown = 0,
+    PtrCast = 1,
+    Node = 2,
+    JsonParse = 3,
+    JsonStringify = 4,
+    UrlParse = 5,
+    Http = 6,
+    Unimplemented = 7,
+    Parse = 8,
+    Crypto = 9,
+    Extractor = 10,
+    Custom = 11,
+}
+
+impl MochiError {
+    pub fn custom<T: Into<String>>(message: T) -> Self {
+        MochiError::Custom(message.into())
+    }
+
+    /// The kind of the error, or of the error it adds context to.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            MochiError::PtrCast(_) => ErrorKind::PtrCast,
+            MochiError::Node(_) => ErrorKind::Node,
+            MochiError::JsonParseError => ErrorKind::JsonParse,
+            MochiError::JsonStringifyError => ErrorKind::JsonStringify,
+            MochiError::UrlParseError => ErrorKind::UrlParse,
+            MochiError::Http { .. } => ErrorKind::Http,
+            MochiError::Parse(_) => ErrorKind::Parse,
+            MochiError::Crypto(_) => ErrorKind::Crypto,
+            MochiError::Extractor(_) => ErrorKind::Extractor,
+            MochiError::Custom(_) => ErrorKind::Custom,
+            MochiError::Context { source, .. } => source.kind(),
+            MochiError::Unimplemented => ErrorKind::Unimplemented,
+            MochiError::Unknown => ErrorKind::Unknown,
+        }
+    }
+
+    /// The error without any context added to it.
+    pub fn root_cause(&self) -> &MochiError {
+        match self {
+            MochiError::Context { source, .. } => source.root_cause(),
+            error => error,
+        }
+    }
+}
+
+/// Context is written before the error, as in `while fetching episodes:
+/// request to https://... failed with status 404`.
+impl fmt::Display for MochiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MochiError::PtrCast(error) => write!(f, "invalid host value: {}", error),
+            MochiError::Node(error) => write!(f, "html error: {}", error),
+            MochiError::JsonParseError => write!(f, "invalid json"),
+            MochiError::JsonStringifyError => write!(f, "value cannot be converted to json"),
+            MochiError::UrlParseError => write!(f, "invalid url"),
+            MochiError::Http { status: -1, url } => write!(f, "request to {} could not be sent", url),
+            MochiError::Http { status, url } => write!(f, "request to {} failed with status {}", url, status),
+            MochiError::Parse(message) => write!(f, "parse error: {}", message),
+            MochiError::Crypto(message) => write!(f, "crypto error: {}", message),
+            MochiError::Extractor(message) => write!(f, "extractor error: {}", message),
+            MochiError::Custom(message) => f.write_str(message),
+            MochiError::Context { context, source } => write!(f, "{}: {}", context, source),
+            MochiError::Unimplemented => write!(f, "not implemented"),
+            MochiError::Unknown => write!(f, "unknown error"),
+        }
+    }
+}
+
+/// Adds what was being done to an error, e.g.
+/// `.context("while fetching the episode list")?`.
+pub trait Context<T> {
+    fn context<C: Into<String>>(self, context: C) -> Result<T>;
+
+    /// Same as [context](Self::context), but only builds the context on error.
+    fn with_context<C: Into<String>, F: FnOnce() -> C>(self, f: F) -> Result<T>;
+}
+
+impl<T, E: Into<MochiError>> Context<T> for core::result::Result<T, E> {
+    fn context<C: Into<String>>(self, context: C) -> Result<T> {
+        self.map_err(|error| MochiError::Context {
+            context: context.into(),
+            source: Box::new(error.into()),
+        })
+    }
+
+    fn with_context<C: Into<String>, F: FnOnce() -> C>(self, f: F) -> Result<T> {
+        self.map_err(|error| MochiError::Context {
+            context: f().into(),
+            source: Box::new(error.into()),
+        })
+    }
+}
+
+/// A missing value fails with [MochiError::Custom] holding the context.
+impl<T> Context<T> for Option<T> {
+    fn context<C: Into<String>>(self, context: C) -> Result<T> {
+        self.ok_or_else(|| MochiError::Custom(context.into()))
+    }
+
+    fn with_context<C: Into<String>, F: FnOnce() -> C>(self, f: F) -> Result<T> {
+        self.ok_or_else(|| MochiError::Custom(f().into()))
+    }
+}
+
 impl From<PtrCastError> for MochiError {
     fn from(cast: PtrCastError) -> Self {
         Self::PtrCast(cast)
@@ -25,8 +155,26 @@ impl From<NodeError> for MochiError {
 }
 
 impl From<ParseIntError> for MochiError {
-    fn from(_: ParseIntError) -> Self {
-        Self::Unimplemented
+    fn from(error: ParseIntError) -> Self {
+        Self::Parse(error.to_string())
+    }
+}
+
+impl From<ParseFloatError> for MochiError {
+    fn from(error: ParseFloatError) -> Self {
+        Self::Parse(error.to_string())
+    }
+}
+
+impl From<Utf8Error> for MochiError {
+    fn from(error: Utf8Error) -> Self {
+        Self::Parse(error.to_string())
+    }
+}
+
+impl From<FromUtf8Error> for MochiError {
+    fn from(error: FromUtf8Error) -> Self {
+        Self::Parse(error.to_string())
     }
 }
 
@@ -37,6 +185,15 @@ pub enum NodeError {
     ModifyError
 }
 
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeError::ParserError => write!(f, "html could not be parsed"),
+            NodeError::ModifyError => write!(f, "node could not be modified"),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum PtrCastError {
@@ -50,3 +207,128 @@ pub enum PtrCastError {
     NotNode
 }
 
+
+impl fmt::Display for PtrCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PtrCastError::NullPointer => write!(f, "value is null"),
+            PtrCastError::Utf8NotValid => write!(f, "string is not valid utf-8"),
+            PtrCastError::NotArray => write!(f, "value is not an array"),
+            PtrCastError::NotObject => write!(f, "value is not an object"),
+            PtrCastError::NotString => write!(f, "value is not a string"),
+            PtrCastError::NotNumber => write!(f, "value is not a number"),
+            PtrCastError::NotBool => write!(f, "value is not a bool"),
+            PtrCastError::NotNode => write!(f, "value is not a node"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn displays_each_variant() {
+        let http = |status| MochiError::Http { status, url: String::from("https://a.to/b") };
+        assert_eq!(MochiError::PtrCast(PtrCastError::NotString).to_string(), "invalid host value: value is not a string");
+        assert_eq!(MochiError::Node(NodeError::ParserError).to_string(), "html error: html could not be parsed");
+        assert_eq!(MochiError::JsonParseError.to_string(), "invalid json");
+        assert_eq!(MochiError::JsonStringifyError.to_string(), "value cannot be converted to json");
+        assert_eq!(MochiError::UrlParseError.to_string(), "invalid url");
+        assert_eq!(http(404).to_string(), "request to https://a.to/b failed with status 404");
+        assert_eq!(http(-1).to_string(), "request to https://a.to/b could not be sent");
+        assert_eq!(MochiError::Parse(String::from("bad number")).to_string(), "parse error: bad number");
+        assert_eq!(MochiError::Crypto(String::from("bad key")).to_string(), "crypto error: bad key");
+        assert_eq!(MochiError::Extractor(String::from("no sources")).to_string(), "extractor error: no sources");
+        assert_eq!(MochiError::custom("as is").to_string(), "as is");
+        assert_eq!(MochiError::Unimplemented.to_string(), "not implemented");
+        assert_eq!(MochiError::Unknown.to_string(), "unknown error");
+    }
+
+    #[test]
+    fn displays_http_errors_without_a_url() {
+        let error = MochiError::Http { status: 500, url: String::new() };
+        assert_eq!(error.to_string(), "request to  failed with status 500");
+        let error = MochiError::Http { status: -1, url: String::new() };
+        assert_eq!(error.to_string(), "request to  could not be sent");
+    }
+
+    #[test]
+    fn conversions_keep_the_message() {
+        let int = "12a".parse::<i32>().unwrap_err();
+        assert_eq!(MochiError::from(int.clone()), MochiError::Parse(int.to_string()));
+
+        let float = "1.2.3".parse::<f64>().unwrap_err();
+        assert_eq!(MochiError::from(float.clone()), MochiError::Parse(float.to_string()));
+
+        let bytes = vec![b'a', 0xff];
+        let utf8 = core::str::from_utf8(&bytes).unwrap_err();
+        assert_eq!(MochiError::from(utf8), MochiError::Parse(utf8.to_string()));
+
+        let from_utf8 = String::from_utf8(bytes).unwrap_err();
+        let message = from_utf8.to_string();
+        assert_eq!(MochiError::from(from_utf8), MochiError::Parse(message));
+
+        assert_eq!(MochiError::from(PtrCastError::NotArray), MochiError::PtrCast(PtrCastError::NotArray));
+        assert_eq!(MochiError::from(NodeError::ModifyError), MochiError::Node(NodeError::ModifyError));
+    }
+
+    #[test]
+    fn question_mark_converts_parse_errors() {
+        fn parse(value: &str) -> Result<i32> {
+            Ok(value.parse::<i32>()?)
+        }
+        assert_eq!(parse("7"), Ok(7));
+        assert_eq!(parse("x").unwrap_err().kind(), ErrorKind::Parse);
+    }
+
+    #[test]
+    fn missing_options_become_custom_errors() {
+        assert_eq!(Some(1).context("no episodes"), Ok(1));
+        assert_eq!(None::<i32>.context("no episodes"), Err(MochiError::custom("no episodes")));
+        assert_eq!(None::<i32>.with_context(|| "no episodes"), Err(MochiError::custom("no episodes")));
+
+        let mut built = false;
+        let _ = Some(1).with_context(|| {
+            built = true;
+            "unused"
+        });
+        assert!(!built);
+    }
+
+    #[test]
+    fn context_is_layered_over_the_root_cause() {
+        let result: Result<()> = Err(MochiError::Http { status: 404, url: String::from("https://a.to/ep") });
+        let error = result
+            .context("while fetching episodes")
+            .with_context(|| "while loading the playlist")
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "while loading the playlist: while fetching episodes: request to https://a.to/ep failed with status 404"
+        );
+        assert_eq!(error.kind(), ErrorKind::Http);
+        assert_eq!(
+            error.root_cause(),
+            &MochiError::Http { status: 404, url: String::from("https://a.to/ep") }
+        );
+
+        let MochiError::Context { context, source } = &error else {
+            panic!("expected context, got {:?}", error);
+        };
+        assert_eq!(context, "while loading the playlist");
+        assert_eq!(source.root_cause(), error.root_cause());
+
+        // An error without context is its own root cause.
+        assert_eq!(MochiError::Unknown.root_cause(), &MochiError::Unknown);
+    }
+
+    #[test]
+    fn context_converts_other_errors() {
+        let error = "x".parse::<u8>().context("while reading the season").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Parse);
+        assert_eq!(error.to_string(), "while reading the season: parse error: invalid digit found in string");
+    }
+}
@@ -7,12 +7,15 @@ use crate::imports::core::Kind;
 use core::fmt::Display;
 
 use alloc::string::String;
+use alloc::vec::Vec;
 
-use super::core::{PtrRef, ArrayRef, ptr_kind};
+use super::core::{PtrRef, ArrayRef};
 
 use super::error::{Result, MochiError, NodeError};
+use super::url::Url;
 use super::core::{HostPtr, copy, destroy};
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "html")]
 // #[link(name = "swift-bindings", kind = "static")]
 extern "C" {
@@ -37,6 +40,10 @@ extern "C" {
 
     #[link_name = "select"]
     fn scraper_select(ptr: HostPtr, selector: *const u8, selector_len: usize) -> i32;
+    #[link_name = "select_first"]
+    fn scraper_select_first(ptr: HostPtr, selector: *const u8, selector_len: usize) -> HostPtr;
+    #[link_name = "closest"]
+    fn scraper_closest(ptr: HostPtr, selector: *const u8, selector_len: usize) -> HostPtr;
     #[link_name = "attr"]
     fn scraper_attr(ptr: HostPtr, selector: *const u8, selector_len: usize) -> i32;
 
@@ -57,6 +64,14 @@ extern "C" {
     fn scraper_next(ptr: HostPtr) -> HostPtr;
     #[link_name = "previous"]
     fn scraper_previous(ptr: HostPtr) -> HostPtr;
+    #[link_name = "parent"]
+    fn scraper_parent(ptr: HostPtr) -> HostPtr;
+    #[link_name = "children"]
+    fn scraper_children(ptr: HostPtr) -> HostPtr;
+    #[link_name = "siblings"]
+    fn scraper_siblings(ptr: HostPtr) -> HostPtr;
+    #[link_name = "index_in_parent"]
+    fn scraper_index_in_parent(ptr: HostPtr) -> i32;
 
     #[link_name = "base_uri"]
     fn scraper_base_uri(ptr: HostPtr) -> HostPtr;
@@ -92,8 +107,13 @@ extern "C" {
     fn scraper_has_class(ptr: HostPtr, class_name: *const u8, class_length: usize) -> bool;
     #[link_name = "has_attr"]
     fn scraper_has_attr(ptr: HostPtr, attr_name: *const u8, attr_length: usize) -> bool;
+    #[link_name = "attributes"]
+    fn scraper_attributes(ptr: HostPtr) -> HostPtr;
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::html::*;
+
 /// HTML escape an input string.
 ///
 /// # Examples
@@ -202,6 +222,17 @@ impl Node {
         Self(ptr)
     }
 
+    /// Takes `ptr` if it's a node, for imports returning an optional node.
+    /// Any other value is dropped.
+    fn try_from_ptr(ptr: HostPtr) -> Option<Self> {
+        let ptr_ref = PtrRef::new(ptr);
+        if ptr_ref.kind() != Kind::Node {
+            return None;
+        }
+        core::mem::forget(ptr_ref);
+        Some(Node(ptr))
+    }
+
     #[inline]
     pub fn close(self) {
         drop(self)
@@ -268,10 +299,46 @@ impl Node {
     /// | `:only-of-type`           |  an element that has a parent element and whose parent element has no other element children with the same expanded element name                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                  |                                                        |
     /// | `:empty`                  | elements that have no children at all                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |                                                        |
     /// </details>
-    pub fn select<T: AsRef<str>>(&self, selector: T) -> Self {
+    ///
+    /// # Example
+    /// ```ignore
+    /// for item in document.select(".film_list-wrap .flw-item") {
+    ///     let title = item.select(".film-name a").attr("title");
+    /// }
+    /// ```
+    ///
+    /// # Migrating from `Node`
+    /// `select` used to return a [Node] of every match. [NodeList] keeps
+    /// `text`, `attr`, `html` and `select`, and [as_node](NodeList::as_node)
+    /// gives the old [Node] back, but these calls compile and mean something
+    /// else now:
+    /// - `.next()` is [Iterator::next], the first element left, rather than
+    ///   the sibling after the matches. Use
+    ///   [next_sibling](Node::next_sibling) on a single element instead.
+    /// - `.first()` returns an `Option<Node>`, which is `None` when nothing
+    ///   matched, rather than a [Node].
+    /// - `.last()` is [Iterator::last], which returns an `Option<Node>` too
+    ///   but consumes the list.
+    pub fn select<T: AsRef<str>>(&self, selector: T) -> NodeList {
         let selector = selector.as_ref();
         let host_id = unsafe { scraper_select(self.0, selector.as_ptr(), selector.len()) };
-        Self(host_id)
+        NodeList::from(Self(host_id))
+    }
+
+    /// Find the first element that matches the given CSS selector, returning
+    /// `None` if there isn't one.
+    pub fn select_first<T: AsRef<str>>(&self, selector: T) -> Option<Node> {
+        let selector = selector.as_ref();
+        let ptr = unsafe { scraper_select_first(self.0, selector.as_ptr(), selector.len()) };
+        Node::try_from_ptr(ptr)
+    }
+
+    /// Find the closest element matching the given CSS selector, starting
+    /// with the element itself and going up through its parents.
+    pub fn closest<T: AsRef<str>>(&self, selector: T) -> Option<Node> {
+        let selector = selector.as_ref();
+        let ptr = unsafe { scraper_closest(self.0, selector.as_ptr(), selector.len()) };
+        Node::try_from_ptr(ptr)
     }
 
     /// Get an attribute value by its key.
@@ -373,22 +440,54 @@ impl Node {
 
     /// Get the next sibling of the element, returning `None` if there isn't
     /// one.
-    pub fn next(&self) -> Option<Node> {
+    pub fn next_sibling(&self) -> Option<Node> {
         let ptr = unsafe { scraper_next(self.0) };
-        match unsafe { ptr_kind(ptr) } {
-            Kind::Node => Some(Node(ptr)),
-            _ => None,
-        }
+        Node::try_from_ptr(ptr)
     }
 
     /// Get the previous sibling of the element, returning `None` if there isn't
     /// one.
-    pub fn previous(&self) -> Option<Node> {
+    pub fn previous_sibling(&self) -> Option<Node> {
         let ptr = unsafe { scraper_previous(self.0) };
-        match unsafe { ptr_kind(ptr) } {
-            Kind::Node => Some(Node(ptr)),
-            _ => None,
-        }
+        Node::try_from_ptr(ptr)
+    }
+
+    /// Renamed, as `select(..).next()` is now [Iterator::next] of a [NodeList].
+    #[deprecated(note = "renamed to `next_sibling`")]
+    #[inline]
+    pub fn next(&self) -> Option<Node> {
+        self.next_sibling()
+    }
+
+    #[deprecated(note = "renamed to `previous_sibling`")]
+    #[inline]
+    pub fn previous(&self) -> Option<Node> {
+        self.previous_sibling()
+    }
+
+    /// Get the parent element, returning `None` for the root element.
+    pub fn parent(&self) -> Option<Node> {
+        let ptr = unsafe { scraper_parent(self.0) };
+        Node::try_from_ptr(ptr)
+    }
+
+    /// Get the child elements, leaving out text and comments.
+    pub fn children(&self) -> NodeList {
+        let ptr = unsafe { scraper_children(self.0) };
+        NodeList::from(Node(ptr))
+    }
+
+    /// Get the other elements with the same parent, in document order.
+    pub fn siblings(&self) -> NodeList {
+        let ptr = unsafe { scraper_siblings(self.0) };
+        NodeList::from(Node(ptr))
+    }
+
+    /// Get the position of the element among its parent's child elements,
+    /// returning `None` if it has no parent.
+    pub fn index_in_parent(&self) -> Option<usize> {
+        let index = unsafe { scraper_index_in_parent(self.0) };
+        usize::try_from(index).ok()
     }
 
     /// Get the base URI of this Node
@@ -397,6 +496,11 @@ impl Node {
         PtrRef::new(ptr).as_string().unwrap_or_default()
     }
 
+    /// Resolve a link, like an `href`, against the base URI of this Node.
+    pub fn join_url<T: AsRef<str>>(&self, link: T) -> Result<Url> {
+        Url::parse(self.base_uri())?.join(link)
+    }
+
     /// Get the document's `body` element.
     pub fn body(&self) -> String {
         let ptr = unsafe { scraper_body(self.0) };
@@ -525,6 +629,23 @@ impl Node {
         let attr_name = attr_name.as_ref();
         unsafe { scraper_has_attr(self.0, attr_name.as_ptr(), attr_name.len()) }
     }
+
+    /// Get the element's attributes as `(key, value)` pairs, in no
+    /// particular order.
+    pub fn attributes(&self) -> Vec<(String, String)> {
+        let ptr = unsafe { scraper_attributes(self.0) };
+        let Ok(object) = PtrRef::new(ptr).as_object() else {
+            return Vec::new();
+        };
+        object
+            .keys()
+            .filter_map(|key| key.as_string().ok())
+            .map(|key| {
+                let value = object.get(&key).as_string().unwrap_or_default();
+                (key, value)
+            })
+            .collect()
+    }
 }
 
 impl Display for Node {
@@ -545,4 +666,159 @@ impl Clone for Node {
         let ptr: HostPtr = unsafe { copy(self.0) };
         Self(ptr)
     }
-}
\ No newline at end of file
+}
+
+/// The elements matched by [Node::select], which can be iterated over as
+/// [Node]s.
+///
+/// [text](NodeList::text), [attr](NodeList::attr),
+/// [select](NodeList::select) and [html](NodeList::html) act on all the
+/// matched elements, including ones already iterated over, while the other
+/// methods only see the elements left.
+///
+/// `list.last()` is [Iterator::last], which reads the last element left
+/// directly but consumes the list, so call it on a clone to keep the list.
+#[derive(Debug, Clone)]
+pub struct NodeList {
+    node: Node,
+    array: ArrayRef,
+    front: i32,
+    back: i32,
+}
+
+impl NodeList {
+    /// The number of elements left.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.back - self.front).max(0) as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the element at `index`, returning `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<Node> {
+        if index >= self.len() {
+            return None;
+        }
+        self.array.get(self.front + index as i32).as_node().ok()
+    }
+
+    #[inline]
+    pub fn first(&self) -> Option<Node> {
+        self.get(0)
+    }
+
+    /// Get the text of each element.
+    pub fn text_all(&self) -> Vec<String> {
+        self.clone().map(|node| node.text()).collect()
+    }
+
+    /// Get the value of an attribute for each element, skipping elements
+    /// without it. As with [Node::attr], prefix the key with `abs:` to get
+    /// absolute URLs.
+    pub fn attr_all<T: AsRef<str>>(&self, attr: T) -> Vec<String> {
+        let attr = attr.as_ref();
+        self.clone()
+            .map(|node| node.attr(attr))
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    /// Find elements that match the selector within all the elements. See
+    /// [Node::select] for the supported selectors.
+    #[inline]
+    pub fn select<T: AsRef<str>>(&self, selector: T) -> NodeList {
+        self.node.select(selector)
+    }
+
+    /// Get the combined text of all the elements.
+    #[inline]
+    pub fn text(&self) -> String {
+        self.node.text()
+    }
+
+    /// Get the value of an attribute from the first element that has it.
+    #[inline]
+    pub fn attr<T: AsRef<str>>(&self, attr: T) -> String {
+        self.node.attr(attr)
+    }
+
+    /// Get the combined inner HTML of all the elements.
+    #[inline]
+    pub fn html(&self) -> String {
+        self.node.html()
+    }
+
+    /// All the elements as a single [Node], as `select` used to return.
+    #[inline]
+    pub fn as_node(&self) -> &Node {
+        &self.node
+    }
+}
+
+impl From<Node> for NodeList {
+    fn from(node: Node) -> Self {
+        let array = node.array();
+        let back = array.len().max(0);
+        Self { node, array, front: 0, back }
+    }
+}
+
+impl Iterator for NodeList {
+    type Item = Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.first()?;
+        self.front += 1;
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n.min(i32::MAX as usize) as i32).min(self.back);
+        self.next()
+    }
+
+    /// Gets the last element without going through the others.
+    fn last(self) -> Option<Self::Item> {
+        self.get(self.len().checked_sub(1)?)
+    }
+}
+
+impl DoubleEndedIterator for NodeList {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.get(self.len().checked_sub(1)?)?;
+        self.back -= 1;
+        Some(node)
+    }
+}
+
+impl ExactSizeIterator for NodeList {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{host, reset, Value};
+
+    #[test]
+    fn try_from_ptr_drops_other_values() {
+        reset();
+        let ptr = host::insert(Value::String("not a node".into()));
+        assert!(Node::try_from_ptr(ptr).is_none());
+        assert!(host::value(ptr).is_none());
+
+        let document = Node::new("<p>").unwrap();
+        let live = host::with(|host| host.len());
+        let ptr = unsafe { copy(document.0) };
+        let node = Node::try_from_ptr(ptr).unwrap();
+        assert_eq!(host::with(|host| host.len()), live + 1);
+        drop(node);
+        assert_eq!(host::with(|host| host.len()), live);
+    }
+}
@@ -1,14 +1,34 @@
 extern crate alloc;
 
 use alloc::{vec::Vec, string::String};
+use core::cell::OnceCell;
 
 use super::error::{Result, MochiError, PtrCastError};
 use super::core::PtrRef;
 use super::html::Node;
 use super::json::{parse, JsonValue};
+use super::url::encode_form;
+
+mod cache;
+mod cookie;
+mod multipart;
+mod retry;
+pub use cache::Cache;
+pub use cookie::{Cookie, CookieJar};
+pub use multipart::Multipart;
+pub use retry::RetryPolicy;
+
+/// Forgets what requests keep between calls: the responses [Cache] kept in
+/// memory, the default [RetryPolicy] and when each host was last requested.
+#[cfg(feature = "test-host")]
+pub(crate) fn reset_state() {
+    Cache::clear();
+    retry::reset_state();
+}
 
 type ReqRef = i32;
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "http")]
 // #[link(name = "swift-bindings", kind = "static")]
 extern "C" {
@@ -16,15 +36,19 @@ extern "C" {
     fn request_create(method: RequestMethod) -> ReqRef;
     #[link_name = "send"]
     fn request_send(ptr: ReqRef);
+    #[link_name = "send_all"]
+    fn request_send_all(ptrs: *const ReqRef, len: i32);
+    #[link_name = "reset"]
+    fn request_reset(ptr: ReqRef);
     #[link_name = "close"]
     fn request_close(ptr: ReqRef);
 
     #[link_name = "set_url"]
-    fn request_set_url(ptr: ReqRef, url_ptr: i32, url_len: i32);
+    fn request_set_url(ptr: ReqRef, url_ptr: *const u8, url_len: i32);
     #[link_name = "set_header"]
-    fn request_set_header(ptr: ReqRef, key_ptr: i32, key_len: i32, value_ptr: i32, value_len: i32);
+    fn request_set_header(ptr: ReqRef, key_ptr: *const u8, key_len: i32, value_ptr: *const u8, value_len: i32);
     #[link_name = "set_body"]
-    fn request_set_body(ptr: ReqRef, data_ptr: i32, data_len: i32);
+    fn request_set_body(ptr: ReqRef, data_ptr: *const u8, data_len: i32);
     #[link_name = "set_method"]
     fn request_set_method(ptr: ReqRef, method: RequestMethod);
 
@@ -33,13 +57,47 @@ extern "C" {
     #[link_name = "get_url"]
     fn request_get_url(ptr: ReqRef) -> i32;
     #[link_name = "get_header"]
-    fn request_get_header(ptr: ReqRef, key_ptr: i32, key_len: i32) -> i32;
+    fn request_get_header(ptr: ReqRef, key_ptr: *const u8, key_len: i32) -> i32;
     #[link_name = "get_status_code"]
     fn request_get_status_code(ptr: ReqRef) -> i32;
+    #[link_name = "get_response_url"]
+    fn request_get_response_url(ptr: ReqRef) -> i32;
+    #[link_name = "get_response_headers"]
+    fn request_get_response_headers(ptr: ReqRef) -> i32;
     #[link_name = "get_data_len"]
     fn request_get_data_len(ptr: ReqRef) -> i32;
     #[link_name = "get_data"]
-    fn request_get_data(ptr: ReqRef, arr_ptr: i32, len: i32);
+    fn request_get_data(ptr: ReqRef, arr_ptr: *mut u8, len: i32);
+
+    fn get_cookies() -> i32;
+    fn set_cookies(ptr: i32);
+
+    fn get_cache(key_ptr: *const u8, key_len: i32) -> i32;
+    fn set_cache(key_ptr: *const u8, key_len: i32, value_ptr: i32);
+}
+
+#[cfg(feature = "test-host")]
+use mochi_test::host::http::*;
+
+#[cfg(feature = "test-host")]
+unsafe fn request_create(method: RequestMethod) -> ReqRef {
+    mochi_test::host::http::request_create(method as i32)
+}
+
+#[cfg(feature = "test-host")]
+unsafe fn request_set_method(ptr: ReqRef, method: RequestMethod) {
+    mochi_test::host::http::request_set_method(ptr, method as i32)
+}
+
+#[cfg(feature = "test-host")]
+unsafe fn request_get_method(ptr: ReqRef) -> RequestMethod {
+    match mochi_test::host::http::request_get_method(ptr) {
+        1 => RequestMethod::Post,
+        2 => RequestMethod::Put,
+        3 => RequestMethod::Patch,
+        4 => RequestMethod::Delete,
+        _ => RequestMethod::Get,
+    }
 }
 
 #[repr(C)]
@@ -55,25 +113,159 @@ pub enum RequestMethod {
 #[derive(Debug)]
 pub struct Request {
     ptr: i32,
+    jar: Option<CookieJar>,
+    /// The `Cookie` header set on the request itself, kept when the jar's
+    /// cookies are added.
+    cookie: OnceCell<String>,
+    policy: Option<RetryPolicy>,
+    cache: Option<Cache>,
 }
 
 // By default, the method it uses is `GET`
 impl Request {
-    pub fn new(url: &str, method: RequestMethod) -> Self {
+    /// `url` can be a `&str`, `String` or [Url](super::url::Url).
+    pub fn new<T: AsRef<str>>(url: T, method: RequestMethod) -> Self {
+        let url = url.as_ref();
         unsafe {
             let ptr: i32 = request_create(method);
             request_set_url(
                 ptr,
-                url.as_ptr() as i32, 
+                url.as_ptr(), 
                 url.len() as i32
             );
-            Self { ptr }
+            Self { ptr, jar: None, cookie: OnceCell::new(), policy: None, cache: None }
         }
     }
 
-    #[inline]
-    pub fn send(&self) {
-        unsafe { request_send(self.ptr); }
+    /// Sends the request, retrying it as its [RetryPolicy] says. A request is
+    /// only sent once, so calling this again returns the same response,
+    /// unless it couldn't be sent.
+    pub fn send(&self) -> Response {
+        if let Some(response) = self.cached() {
+            return response;
+        }
+        if self.status_code() != -1 {
+            return self.response();
+        }
+        self.apply_cookies();
+        let response = match self.policy() {
+            Some(policy) => policy.send(self),
+            None => {
+                unsafe { request_send(self.ptr); }
+                self.response()
+            }
+        };
+        self.store(&response);
+        response
+    }
+
+    /// Sends the requests at once, letting the host run them in parallel.
+    /// Each result fails with [MochiError::Http] on its own if that request
+    /// couldn't be sent, so one failure doesn't lose the other responses.
+    ///
+    /// Failed requests are retried one by one as their [RetryPolicy] says,
+    /// but the batch itself isn't rate limited. Requests with a cached
+    /// response aren't sent.
+    pub fn send_all(requests: Vec<Request>) -> Vec<Result<Response>> {
+        let cached: Vec<Option<Response>> = requests.iter().map(Request::cached).collect();
+        let ptrs: Vec<ReqRef> = requests
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(request, _)| {
+                request.apply_cookies();
+                request.ptr
+            })
+            .collect();
+        unsafe { request_send_all(ptrs.as_ptr(), ptrs.len() as i32) };
+        requests
+            .iter()
+            .zip(cached)
+            .map(|(request, cached)| {
+                if let Some(response) = cached {
+                    return Ok(response);
+                }
+                let response = match request.policy() {
+                    Some(policy) => policy.retry(request, request.response()),
+                    None => request.response(),
+                };
+                request.store(&response);
+                if response.status == -1 {
+                    Err(MochiError::Http { status: -1, url: request.url() })
+                } else {
+                    Ok(response)
+                }
+            })
+            .collect()
+    }
+
+    /// A cached response, whose cookies are stored in the jar as if it was
+    /// just received.
+    fn cached(&self) -> Option<Response> {
+        let cache = self.cache.as_ref()?;
+        let response = cache.get(&cache.key(self))?;
+        if let Some(jar) = &self.jar {
+            jar.store(&response);
+        }
+        Some(response)
+    }
+
+    fn store(&self, response: &Response) {
+        if let Some(cache) = &self.cache {
+            cache.set(&cache.key(self), response);
+        }
+    }
+
+    /// Sets the `Cookie` header to the jar's cookies for the URL the request
+    /// is about to be sent to, after any set with a `Cookie` header.
+    fn apply_cookies(&self) {
+        let Some(jar) = &self.jar else {
+            return;
+        };
+        let own = self.cookie.get_or_init(|| self.get_header("Cookie").unwrap_or_default());
+        let cookies = match jar.header(&self.url()) {
+            Some(cookies) if own.is_empty() => cookies,
+            Some(cookies) => alloc::format!("{}; {}", own, cookies),
+            None => own.clone(),
+        };
+        // A header the jar set for an earlier URL is emptied.
+        if !cookies.is_empty() || self.get_header("Cookie").is_ok() {
+            self.set_header("Cookie", &cookies);
+        }
+    }
+
+    fn policy(&self) -> Option<RetryPolicy> {
+        self.policy.clone().or_else(RetryPolicy::default_policy)
+    }
+
+    fn response(&self) -> Response {
+        let status = self.status_code();
+        let url = PtrRef::new(unsafe { request_get_response_url(self.ptr) })
+            .as_string()
+            .unwrap_or_else(|_| self.url());
+        let headers = PtrRef::new(unsafe { request_get_response_headers(self.ptr) })
+            .as_array()
+            .map(|headers| {
+                headers
+                    .filter_map(|pair| {
+                        let pair = pair.as_array().ok()?;
+                        Some((pair.get(0).as_string().ok()?, pair.get(1).as_string().ok()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // `-1` when there is no response.
+        let size = unsafe { request_get_data_len(self.ptr) }.max(0);
+        let mut data = Vec::with_capacity(size as usize);
+        unsafe {
+            request_get_data(self.ptr, data.as_mut_ptr(), size);
+            data.set_len(size as usize);
+        }
+        let response = Response { status, url, headers, data };
+        if let Some(jar) = &self.jar {
+            jar.store(&response);
+        }
+        response
     }
 
     #[inline]
@@ -86,7 +278,7 @@ impl Request {
         unsafe {
             request_set_url(
                 self.ptr, 
-                url.as_ptr() as i32,
+                url.as_ptr(),
                 url.len() as i32
             );
         };
@@ -94,17 +286,43 @@ impl Request {
     }
 
     pub fn header<T: AsRef<str>>(self, key: T, value: T) -> Self {
-        let key = key.as_ref();
-        let value = value.as_ref();
+        self.set_header(key.as_ref(), value.as_ref());
+        self
+    }
+
+    fn set_header(&self, key: &str, value: &str) {
         unsafe {
             request_set_header(
-                self.ptr as i32, 
-                key.as_ptr() as i32, 
+                self.ptr, 
+                key.as_ptr(), 
                 key.len() as i32, 
-                value.as_ptr() as i32, 
+                value.as_ptr(), 
                 value.len() as i32
             )
         };
+    }
+
+    /// Sends the jar's cookies for the request URL, and stores the cookies
+    /// set by the response in it. Cookies already set with a `Cookie` header
+    /// are kept.
+    ///
+    /// The cookies are picked when the request is sent, so they match the
+    /// URL it's sent to.
+    pub fn cookie_jar(mut self, jar: &CookieJar) -> Self {
+        self.jar = Some(jar.clone());
+        self
+    }
+
+    /// Overrides the default [RetryPolicy] for this request. Use
+    /// [RetryPolicy::none] to never retry it.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Reuses a response to the same request while it's in the [Cache].
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
         self
     }
 
@@ -113,13 +331,31 @@ impl Request {
         unsafe { 
             request_set_body(
                 self.ptr, 
-                data.as_ptr() as i32, 
+                data.as_ptr(), 
                 data.len() as i32
             ) 
         };
         self
     }
 
+    /// Sends `fields` like an HTML form, as `application/x-www-form-urlencoded`.
+    pub fn form<K: AsRef<str>, V: AsRef<str>>(self, fields: &[(K, V)]) -> Self {
+        self.header("Content-Type", "application/x-www-form-urlencoded")
+            .body(encode_form(fields))
+    }
+
+    /// Sends `value` as JSON, failing if it can't be serialized.
+    pub fn json_body(self, value: &PtrRef) -> Result<Self> {
+        let json = value.to_json_string()?;
+        Ok(self.header("Content-Type", "application/json").body(json))
+    }
+
+    /// Sends a `multipart/form-data` body.
+    pub fn multipart(self, form: Multipart) -> Self {
+        let (content_type, body) = form.encode();
+        self.header("Content-Type", content_type.as_str()).body(body)
+    }
+
     pub fn set_method(self, method: RequestMethod) -> Self {
         unsafe {
             request_set_method(self.ptr, method)
@@ -127,6 +363,7 @@ impl Request {
         self
     }
 
+    /// The status of the response, or `-1` if it wasn't sent.
     #[inline]
     pub fn status_code(&self) -> i32 {
         unsafe {
@@ -140,12 +377,13 @@ impl Request {
         }
     }
 
+    /// A header set on the request. Use [Response::header] for response headers.
     pub fn get_header<T: AsRef<str>>(&self, key: T) -> Result<String> {
         let key = key.as_ref();
         let value_ptr = unsafe {
             request_get_header(
                 self.ptr, 
-                key.as_ptr() as i32, 
+                key.as_ptr(), 
                 key.len() as i32
             )
         };
@@ -160,37 +398,19 @@ impl Request {
     }
 
     pub fn data(self) -> Vec<u8> {
-        self.send();
-        let size = unsafe { request_get_data_len(self.ptr) };
-        let mut buf = Vec::with_capacity(size as usize);
-        unsafe {
-            request_get_data(self.ptr, buf.as_mut_ptr() as i32, size);
-            buf.set_len(size as usize);
-        }
-        self.close();
-        buf
+        self.send().into_data()
     }
 
     pub fn string(self) -> Result<String> {
-        match String::from_utf8(self.data()) {
-            Ok(v) => Ok(v),
-            Err(_) => Err(MochiError::from(PtrCastError::Utf8NotValid)),
-        }
+        self.send().string()
     }
 
     pub fn json(self) -> Result<JsonValue> {
-        parse(self.data())
+        self.send().json()
     }
 
     pub fn html(self) -> Result<Node> {
-        let url = self.url();
-        let data = self.data();
-
-        if url.is_empty() {
-            Node::new(data)
-        } else {
-            Node::new_with_uri(data, url)
-        }
+        self.send().html()
     }
 }
 
@@ -198,4 +418,100 @@ impl Drop for Request {
     fn drop(&mut self) {
         self.close();
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone)]
+pub struct Response {
+    status: i32,
+    url: String,
+    headers: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl Response {
+    /// The status code, or `-1` if the request couldn't be sent.
+    #[inline]
+    pub fn status_code(&self) -> i32 {
+        self.status
+    }
+
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Fails with [MochiError::Http] when the server answered with a `4xx` or
+    /// `5xx` status, such as a Cloudflare challenge or error page.
+    ///
+    /// It also fails, with a status of `-1`, when the request couldn't be
+    /// sent at all and there is no response, e.g. the host was unreachable.
+    pub fn error_for_status(self) -> Result<Self> {
+        if (0..400).contains(&self.status) {
+            Ok(self)
+        } else {
+            Err(MochiError::Http { status: self.status, url: self.url })
+        }
+    }
+
+    /// The URL of the response, after following redirects.
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// All headers, in the order they were received.
+    #[inline]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The first value of a header, ignoring case.
+    pub fn header<T: AsRef<str>>(&self, key: T) -> Option<&str> {
+        self.header_all(key).next()
+    }
+
+    /// Every value of a header that can be repeated, like `Set-Cookie`.
+    pub fn header_all<T: AsRef<str>>(&self, key: T) -> impl Iterator<Item = &str> {
+        let key = String::from(key.as_ref());
+        self.headers
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(&key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The media type of the `Content-Type` header without parameters,
+    /// e.g. `text/html`.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("Content-Type")
+            .map(|value| value.split(';').next().unwrap_or_default().trim())
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn string(&self) -> Result<String> {
+        match core::str::from_utf8(&self.data) {
+            Ok(v) => Ok(String::from(v)),
+            Err(_) => Err(MochiError::from(PtrCastError::Utf8NotValid)),
+        }
+    }
+
+    pub fn json(&self) -> Result<JsonValue> {
+        parse(&self.data)
+    }
+
+    /// Parses the body, resolving relative links against the final URL.
+    pub fn html(&self) -> Result<Node> {
+        if self.url.is_empty() {
+            Node::new(&self.data)
+        } else {
+            Node::new_with_uri(&self.data, &self.url)
+        }
+    }
+}
@@ -0,0 +1,165 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::macros::state::global_state;
+use super::super::core::{now, ArrayRef, ObjectRef, PtrRef};
+use super::super::crypto::Crypto;
+use super::{get_cache, set_cache, Request, Response};
+
+/// Keeps successful responses of a [Request] for a while, so the same request
+/// doesn't hit the network again. Set with [Request::cache].
+///
+/// Responses are kept in memory until their TTL passes or the source is
+/// unloaded, and optionally on the host with [persist](Self::persist).
+///
+/// ```ignore
+/// let key = Request::new(KEY_URL, RequestMethod::Get)
+///     .cache(Cache::new(60 * 60).persist())
+///     .string()?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    ttl: u32,
+    vary: Vec<String>,
+    persist: bool,
+}
+
+impl Cache {
+    /// Uses responses that are at most `ttl` seconds old.
+    pub fn new(ttl: u32) -> Self {
+        Self {
+            ttl,
+            vary: Vec::new(),
+            persist: false,
+        }
+    }
+
+    /// Request headers that are part of the key, besides the method and URL,
+    /// e.g. `Referer` when it changes the response.
+    pub fn vary<T: AsRef<str>>(mut self, headers: &[T]) -> Self {
+        self.vary = headers.iter().map(|header| String::from(header.as_ref())).collect();
+        self
+    }
+
+    /// Also stores responses on the host, so they're kept when the source is
+    /// loaded again.
+    pub fn persist(mut self) -> Self {
+        self.persist = true;
+        self
+    }
+
+    /// Forgets every response kept in memory.
+    pub fn clear() {
+        with_state(|cache| cache.clear());
+    }
+
+    pub(super) fn key(&self, request: &Request) -> String {
+        let mut key = format!("{:?} {}", request.get_method(), request.url());
+        for header in &self.vary {
+            let value = request.get_header(header).unwrap_or_default();
+            key.push_str(&format!("\n{}: {}", header.to_ascii_lowercase(), value));
+        }
+        key
+    }
+
+    /// The response stored for `key` if it's fresh. Responses kept in memory
+    /// past the TTL they were stored with are dropped.
+    pub(super) fn get(&self, key: &str) -> Option<Response> {
+        let now = now();
+        let fresh = |stored: f64| now - stored < self.ttl_millis();
+        let cached = with_state(|cache| {
+            cache.retain(|_, entry| entry.expires > now);
+            match cache.get(key) {
+                Some(entry) if fresh(entry.stored) => Some(entry.response.clone()),
+                _ => None,
+            }
+        });
+        if cached.is_some() || !self.persist {
+            return cached;
+        }
+
+        let (stored, response) = decode(PtrRef::new(unsafe { get_cache(key.as_ptr(), key.len() as i32) }))?;
+        if !fresh(stored) {
+            return None;
+        }
+        self.keep(key, stored, &response);
+        Some(response)
+    }
+
+    /// Stores `response` if it was successful.
+    pub(super) fn set(&self, key: &str, response: &Response) {
+        if !response.is_success() {
+            return;
+        }
+        let stored = now();
+        self.keep(key, stored, response);
+        if self.persist {
+            let value = encode(stored, response);
+            unsafe { set_cache(key.as_ptr(), key.len() as i32, value.pointer()) };
+        }
+    }
+
+    fn ttl_millis(&self) -> f64 {
+        self.ttl as f64 * 1000.0
+    }
+
+    fn keep(&self, key: &str, stored: f64, response: &Response) {
+        let entry = Entry {
+            stored,
+            expires: stored + self.ttl_millis(),
+            response: response.clone(),
+        };
+        with_state(|cache| cache.insert(String::from(key), entry));
+    }
+}
+
+struct Entry {
+    stored: f64,
+    /// When the entry is stale for the [Cache] that stored it.
+    expires: f64,
+    response: Response,
+}
+
+/// Responses are stored with when they were received.
+fn encode(stored: f64, response: &Response) -> PtrRef {
+    let mut object = ObjectRef::new();
+    object.set("stored", stored.into());
+    object.set("status", response.status.into());
+    object.set("url", response.url.as_str().into());
+    let headers: ArrayRef = response
+        .headers
+        .iter()
+        .map(|(key, value)| PtrRef::from(alloc::vec![key.as_str(), value.as_str()]))
+        .collect();
+    object.set("headers", headers.into());
+    object.set("body", Crypto::base64_string(&response.data).into());
+    object.into()
+}
+
+fn decode(value: PtrRef) -> Option<(f64, Response)> {
+    let object = value.as_object().ok()?;
+    let headers = object
+        .get("headers")
+        .as_array()
+        .ok()?
+        .filter_map(|pair| {
+            let pair = pair.as_array().ok()?;
+            Some((pair.get(0).as_string().ok()?, pair.get(1).as_string().ok()?))
+        })
+        .collect();
+    let response = Response {
+        status: object.get("status").as_int().ok()? as i32,
+        url: object.get("url").as_string().ok()?,
+        headers,
+        data: Crypto::base64_parse(&object.get("body").as_string().ok()?),
+    };
+    Some((object.get("stored").as_float().ok()?, response))
+}
+
+global_state! {
+    fn with_state() -> BTreeMap<String, Entry> = BTreeMap::new();
+}
@@ -0,0 +1,337 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use super::super::core::{now, ObjectRef, PtrRef};
+use super::super::url::Url;
+use super::{get_cookies, set_cookies, Response};
+
+/// A cookie set by a site, scoped to a domain and path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// Lowercase domain, without a leading dot.
+    pub domain: String,
+    /// Whether only `domain` itself matches, and not its subdomains. This is
+    /// the case when `Set-Cookie` has no `Domain` attribute.
+    pub host_only: bool,
+    pub path: String,
+    pub secure: bool,
+    pub http_only: bool,
+    /// When the cookie expires, in milliseconds since the Unix epoch, or
+    /// `None` for a session cookie.
+    pub expires: Option<i64>,
+}
+
+impl Cookie {
+    /// Parses a `Set-Cookie` header received from `url`. Returns `None` if the
+    /// header is malformed or sets a cookie for another domain.
+    ///
+    /// `Max-Age` is counted from [now], and takes precedence over `Expires`.
+    /// A cookie that has already expired is still returned, see
+    /// [is_expired](Self::is_expired).
+    pub fn parse<T: AsRef<str>>(set_cookie: T, url: &str) -> Option<Self> {
+        Self::parse_at(set_cookie.as_ref(), url, now() as i64)
+    }
+
+    fn parse_at(set_cookie: &str, url: &str, now: i64) -> Option<Self> {
+        let (_, host, request_path) = split_url(url)?;
+        let mut attributes = set_cookie.split(';');
+        let (name, value) = attributes.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(&request_path),
+            secure: false,
+            http_only: false,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attribute in attributes {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "expires" => cookie.expires = parse_date(value).or(cookie.expires),
+                "max-age" => max_age = value.parse::<i64>().ok().or(max_age),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                _ => {}
+            }
+        }
+        if let Some(max_age) = max_age {
+            // A `Max-Age` of zero or less expires the cookie right away.
+            cookie.expires = Some(if max_age <= 0 {
+                i64::MIN
+            } else {
+                now.saturating_add(max_age.saturating_mul(1000))
+            });
+        }
+        Some(cookie)
+    }
+
+    /// Whether the cookie has expired, according to the host's [now].
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(now() as i64)
+    }
+
+    fn is_expired_at(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Whether the cookie should be sent with a request to `url`.
+    pub fn matches(&self, url: &str) -> bool {
+        let Some((scheme, host, path)) = split_url(url) else {
+            return false;
+        };
+        let domain = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        domain && path_matches(&path, &self.path) && (!self.secure || scheme == "https")
+    }
+}
+
+impl From<Cookie> for PtrRef {
+    fn from(cookie: Cookie) -> Self {
+        let mut object = ObjectRef::new();
+        object.set("name", cookie.name.into());
+        object.set("value", cookie.value.into());
+        object.set("domain", cookie.domain.into());
+        object.set("hostOnly", cookie.host_only.into());
+        object.set("path", cookie.path.into());
+        object.set("secure", cookie.secure.into());
+        object.set("httpOnly", cookie.http_only.into());
+        if let Some(expires) = cookie.expires {
+            object.set("expires", expires.into());
+        }
+        object.into()
+    }
+}
+
+impl Into<Cookie> for PtrRef {
+    fn into(self) -> Cookie {
+        let object = self.as_object().unwrap_or_default();
+        Cookie {
+            name: object.get("name").into(),
+            value: object.get("value").into(),
+            domain: object.get("domain").into(),
+            host_only: object.get("hostOnly").into(),
+            path: object.get("path").into(),
+            secure: object.get("secure").into(),
+            http_only: object.get("httpOnly").into(),
+            expires: object.get("expires").as_int().ok(),
+        }
+    }
+}
+
+/// Cookies collected from responses, which are sent with later requests to
+/// matching domains and paths.
+///
+/// Clones share the same cookies, so a jar can be handed to each
+/// [Request](super::Request) with [cookie_jar](super::Request::cookie_jar).
+///
+/// ```ignore
+/// let jar = CookieJar::load();
+/// Request::new(&login_url, RequestMethod::Post).cookie_jar(&jar).send();
+/// let page = Request::new(&page_url, RequestMethod::Get).cookie_jar(&jar).html()?;
+/// jar.save();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar(Rc<RefCell<Vec<Cookie>>>);
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The jar last saved with [save](Self::save), or an empty one.
+    pub fn load() -> Self {
+        let cookies = PtrRef::new(unsafe { get_cookies() });
+        match cookies.as_array() {
+            Ok(array) => Self(Rc::new(RefCell::new(array.map(|cookie| cookie.into()).collect()))),
+            Err(_) => Self::new(),
+        }
+    }
+
+    /// Keeps the cookies on the host, so they can be loaded by a later call
+    /// into the source.
+    pub fn save(&self) {
+        let cookies = PtrRef::from(self.cookies());
+        unsafe { set_cookies(cookies.pointer()) };
+    }
+
+    /// Adds a cookie, replacing one with the same name, domain and path.
+    pub fn insert(&self, cookie: Cookie) {
+        let mut cookies = self.0.borrow_mut();
+        cookies.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path));
+        cookies.push(cookie);
+    }
+
+    /// Stores a `Set-Cookie` header received from `url`. An expired cookie,
+    /// e.g. one with `Max-Age=0`, removes the cookie it would replace.
+    pub fn set_cookie<T: AsRef<str>>(&self, url: &str, set_cookie: T) {
+        if let Some(cookie) = Cookie::parse(set_cookie, url) {
+            if cookie.is_expired() {
+                self.0.borrow_mut().retain(|c| {
+                    !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+                });
+            } else {
+                self.insert(cookie);
+            }
+        }
+    }
+
+    /// Stores every `Set-Cookie` header of a response.
+    pub fn store(&self, response: &Response) {
+        for set_cookie in response.header_all("Set-Cookie") {
+            self.set_cookie(response.url(), set_cookie);
+        }
+    }
+
+    /// The first cookie named `name`.
+    pub fn get(&self, name: &str) -> Option<Cookie> {
+        self.0.borrow().iter().find(|c| c.name == name).cloned()
+    }
+
+    /// Removes every cookie named `name`.
+    pub fn remove(&self, name: &str) {
+        self.0.borrow_mut().retain(|c| c.name != name);
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+
+    pub fn cookies(&self) -> Vec<Cookie> {
+        self.0.borrow().clone()
+    }
+
+    /// The value of a `Cookie` header for `url`, with more specific paths
+    /// first, or `None` if no cookie matches. Expired cookies are dropped.
+    pub fn header(&self, url: &str) -> Option<String> {
+        let now = now() as i64;
+        self.0.borrow_mut().retain(|c| !c.is_expired_at(now));
+        let cookies = self.0.borrow();
+        let mut matching: Vec<&Cookie> = cookies.iter().filter(|c| c.matches(url)).collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|c| core::cmp::Reverse(c.path.len()));
+        let pairs: Vec<String> = matching
+            .into_iter()
+            .map(|c| alloc::format!("{}={}", c.name, c.value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+}
+
+/// Scheme, lowercase host and path of a URL.
+fn split_url(url: &str) -> Option<(String, String, String)> {
+    let url = Url::parse(url).ok()?;
+    if url.host().is_empty() {
+        return None;
+    }
+    Some((url.scheme().to_string(), url.host().to_string(), url.path().to_string()))
+}
+
+/// The directory of the request path, as in RFC 6265 5.1.4.
+fn default_path(path: &str) -> String {
+    match path.rfind('/') {
+        Some(0) | None => String::from("/"),
+        Some(index) => path[..index].to_string(),
+    }
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.len() > domain.len()
+            && host.ends_with(domain)
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.')
+}
+
+fn path_matches(path: &str, cookie_path: &str) -> bool {
+    path == cookie_path
+        || (path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || path.as_bytes().get(cookie_path.len()) == Some(&b'/')))
+}
+
+/// Parses a cookie date, as in RFC 6265 5.1.1, into milliseconds since the
+/// Unix epoch. Accepts `Wed, 21 Oct 2015 07:28:00 GMT` and the older
+/// `Wednesday, 21-Oct-15 07:28:00 GMT` and asctime forms.
+fn parse_date(date: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+    let mut time = None;
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+
+    let tokens = date.split(|c: char| !(c.is_ascii_alphanumeric() || c == ':')).filter(|t| !t.is_empty());
+    for token in tokens {
+        if time.is_none() {
+            let mut parts = token.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+            if let (Some(Some(h)), Some(Some(m)), Some(Some(s))) = (parts.next(), parts.next(), parts.next()) {
+                time = Some((h, m, s));
+                continue;
+            }
+        }
+        let digits = token.bytes().take_while(u8::is_ascii_digit).count();
+        if day.is_none() && (1..=2).contains(&digits) {
+            day = token[..digits].parse::<i64>().ok();
+            continue;
+        }
+        if month.is_none() && token.len() >= 3 {
+            let prefix = token[..3].to_ascii_lowercase();
+            if let Some(index) = MONTHS.iter().position(|m| *m == prefix) {
+                month = Some(index as i64 + 1);
+                continue;
+            }
+        }
+        if year.is_none() && (2..=4).contains(&digits) {
+            year = token[..digits].parse::<i64>().ok();
+        }
+    }
+
+    let (hour, minute, second) = time?;
+    let (day, month) = (day?, month?);
+    let year = match year? {
+        year @ 70..=99 => year + 1900,
+        year @ 0..=69 => year + 2000,
+        year => year,
+    };
+    if year < 1601 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    // Days since the epoch of a proleptic Gregorian date.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    Some(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000)
+}
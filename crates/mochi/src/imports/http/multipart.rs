@@ -0,0 +1,117 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::super::crypto::Crypto;
+
+struct Part {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+    data: Vec<u8>,
+}
+
+/// A `multipart/form-data` body, as used by forms that upload files. Sent
+/// with [Request::multipart](super::Request::multipart).
+///
+/// ```ignore
+/// let form = Multipart::new()
+///     .text("type", "image")
+///     .file("file", "cover.jpg", "image/jpeg", bytes);
+/// let json = Request::new(&url, RequestMethod::Post).multipart(form).json()?;
+/// ```
+#[derive(Default)]
+pub struct Multipart {
+    parts: Vec<Part>,
+}
+
+impl core::fmt::Debug for Multipart {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.parts.iter().map(|part| &part.name))
+            .finish()
+    }
+}
+
+impl Multipart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text<A: AsRef<str>, B: AsRef<str>>(mut self, name: A, value: B) -> Self {
+        self.parts.push(Part {
+            name: String::from(name.as_ref()),
+            file_name: None,
+            content_type: None,
+            data: Vec::from(value.as_ref().as_bytes()),
+        });
+        self
+    }
+
+    pub fn file<A: AsRef<str>, B: AsRef<str>, C: AsRef<str>, D: AsRef<[u8]>>(
+        mut self,
+        name: A,
+        file_name: B,
+        content_type: C,
+        data: D,
+    ) -> Self {
+        self.parts.push(Part {
+            name: String::from(name.as_ref()),
+            file_name: Some(String::from(file_name.as_ref())),
+            content_type: Some(String::from(content_type.as_ref())),
+            data: Vec::from(data.as_ref()),
+        });
+        self
+    }
+
+    /// The `Content-Type` header and the body.
+    pub(super) fn encode(&self) -> (String, Vec<u8>) {
+        let boundary = self.boundary();
+        let mut body = Vec::new();
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            let mut disposition = format!("Content-Disposition: form-data; name=\"{}\"", quote(&part.name));
+            if let Some(file_name) = &part.file_name {
+                disposition.push_str(&format!("; filename=\"{}\"", quote(file_name)));
+            }
+            body.extend_from_slice(disposition.as_bytes());
+            body.extend_from_slice(b"\r\n");
+            if let Some(content_type) = &part.content_type {
+                body.extend_from_slice(format!("Content-Type: {}\r\n", content_type).as_bytes());
+            }
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+        (format!("multipart/form-data; boundary={}", boundary), body)
+    }
+
+    /// A random boundary that doesn't occur in any part. The attempt is
+    /// appended too, in case the host has no randomness to give.
+    fn boundary(&self) -> String {
+        let mut attempt = 0;
+        loop {
+            let random: String = Crypto::generate_random_bytes(12)
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            let boundary = format!("----MochiFormBoundary{}{:x}", random, attempt);
+            let conflict = self
+                .parts
+                .iter()
+                .any(|part| part.data.windows(boundary.len()).any(|window| window == boundary.as_bytes()));
+            if !conflict {
+                return boundary;
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// Escapes a name or file name, the way browsers do.
+fn quote(text: &str) -> String {
+    text.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
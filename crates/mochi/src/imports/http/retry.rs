@@ -0,0 +1,201 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::macros::state::global_state;
+use super::super::core::{now, sleep};
+use super::super::url::Url;
+use super::{request_reset, request_send, Request, Response};
+
+/// How a [Request] is retried when it fails, and how fast requests to a
+/// host may be sent.
+///
+/// A policy can be set for every request of a source with
+/// [set_default](Self::set_default), and for a single request with
+/// [Request::retry].
+///
+/// ```ignore
+/// RetryPolicy::new()
+///     .max_retries(5)
+///     .retry_on(&[429, 503])
+///     .rate_limit(2.0)
+///     .set_default();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: u32,
+    max_backoff: u32,
+    statuses: Vec<i32>,
+    requests_per_second: Option<f64>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Retries up to 3 times after 500ms, 1s and 2s, on `429`, `500`, `502`,
+    /// `503` and `504` or when the request couldn't be sent. There's no rate
+    /// limit.
+    pub fn new() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: 500,
+            max_backoff: 10_000,
+            statuses: vec![-1, 429, 500, 502, 503, 504],
+            requests_per_second: None,
+        }
+    }
+
+    /// A policy that never retries or waits, which is how requests are sent
+    /// without one.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            statuses: Vec::new(),
+            ..Self::new()
+        }
+    }
+
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Milliseconds before the first retry, doubled for each one after it.
+    pub fn backoff(mut self, millis: u32) -> Self {
+        self.backoff = millis;
+        self
+    }
+
+    /// The longest wait between retries, including one asked for with a
+    /// `Retry-After` header.
+    pub fn max_backoff(mut self, millis: u32) -> Self {
+        self.max_backoff = millis;
+        self
+    }
+
+    /// Status codes to retry, where `-1` is a request that couldn't be sent.
+    pub fn retry_on(mut self, statuses: &[i32]) -> Self {
+        self.statuses = statuses.to_vec();
+        self
+    }
+
+    /// Spaces out requests to the same host so there are at most
+    /// `requests_per_second`.
+    pub fn rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.requests_per_second = Some(requests_per_second).filter(|rps| *rps > 0.0);
+        self
+    }
+
+    /// Uses this policy for requests without their own.
+    pub fn set_default(self) {
+        with_state(|state| state.default = Some(self));
+    }
+
+    /// The policy set with [set_default](Self::set_default), if any.
+    pub fn default_policy() -> Option<Self> {
+        with_state(|state| state.default.clone())
+    }
+
+    /// Goes back to sending requests without a policy by default.
+    pub fn clear_default() {
+        with_state(|state| state.default = None);
+    }
+
+    pub(super) fn send(&self, request: &Request) -> Response {
+        self.throttle(&request.url());
+        unsafe { request_send(request.ptr) };
+        self.retry(request, request.response())
+    }
+
+    /// Sends `request` again while `response` has a status to retry.
+    pub(super) fn retry(&self, request: &Request, mut response: Response) -> Response {
+        let mut attempt = 0;
+        while attempt < self.max_retries && self.statuses.contains(&response.status_code()) {
+            sleep(self.delay(attempt, &response));
+            unsafe { request_reset(request.ptr) };
+            // Cookies set by the failed response, like a challenge's, are sent.
+            request.apply_cookies();
+            self.throttle(&request.url());
+            unsafe { request_send(request.ptr) };
+            response = request.response();
+            attempt += 1;
+        }
+        response
+    }
+
+    fn delay(&self, attempt: u32, response: &Response) -> u32 {
+        let backoff = self.backoff.saturating_mul(2u32.saturating_pow(attempt));
+        // Only the number of seconds is supported, not an HTTP date.
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .map(|seconds| seconds.saturating_mul(1000))
+            .unwrap_or(0);
+        backoff.max(retry_after).min(self.max_backoff)
+    }
+
+    /// Waits until another request to the host of `url` is allowed.
+    fn throttle(&self, url: &str) {
+        let Some(requests_per_second) = self.requests_per_second else {
+            return;
+        };
+        let interval = 1000.0 / requests_per_second;
+        let host = host(url);
+        let wait = with_state(|state| {
+            let now = now();
+            let next = match state.last_requests.iter_mut().find(|(h, _)| *h == host) {
+                Some((_, last)) => {
+                    let next = (*last + interval).max(now);
+                    *last = next;
+                    next
+                }
+                None => {
+                    state.last_requests.push((host, now));
+                    now
+                }
+            };
+            next - now
+        });
+        if wait > 0.0 {
+            // Rounded up, so requests are never closer than the interval.
+            let millis = wait as u32;
+            sleep(if (millis as f64) < wait { millis + 1 } else { millis });
+        }
+    }
+}
+
+fn host(url: &str) -> String {
+    Url::parse(url).map(|url| String::from(url.host())).unwrap_or_default()
+}
+
+struct State {
+    default: Option<RetryPolicy>,
+    /// When the last request to each host was allowed.
+    last_requests: Vec<(String, f64)>,
+}
+
+impl State {
+    const fn new() -> Self {
+        Self {
+            default: None,
+            last_requests: Vec::new(),
+        }
+    }
+}
+
+global_state! {
+    fn with_state() -> State = State::new();
+}
+
+/// Forgets the default policy and when each host was last requested.
+#[cfg(feature = "test-host")]
+pub(super) fn reset_state() {
+    with_state(|state| *state = State::new());
+}
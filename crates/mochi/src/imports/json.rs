@@ -1,19 +1,83 @@
-use super::core::PtrRef;
+extern crate alloc;
+
+use alloc::string::String;
+
+use super::core::{ArrayRef, ObjectRef, PtrRef};
 use super::error::{Result, MochiError};
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "json")]
 // #[link(name = "swift-bindings", kind = "static")]
 extern "C" {
-    fn json_parse(bytes: i32, size: i32) -> i32;
+    fn json_parse(bytes: *const u8, size: i32) -> i32;
+    fn json_stringify(ptr: i32, pretty: bool) -> i32;
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::json::*;
+
 pub type JsonValue = PtrRef;
 
 pub fn parse<T: AsRef<[u8]>>(buf: T) -> Result<JsonValue> {
     let buf = buf.as_ref();
-    let ptr: i32 = unsafe { json_parse(buf.as_ptr() as i32, buf.len() as i32) };
+    let ptr: i32 = unsafe { json_parse(buf.as_ptr(), buf.len() as i32) };
     match ptr {
         -1 => Err(MochiError::JsonParseError),
         _ => Ok(PtrRef::new(ptr))
     }
-}
\ No newline at end of file
+}
+
+/// Serializes a host value to JSON, e.g. to send an [ObjectRef] built in rust
+/// as a request body.
+pub fn stringify(value: &PtrRef) -> Result<String> {
+    stringify_ptr(value.pointer(), false)
+}
+
+/// Same as [stringify], but indented for reading.
+pub fn stringify_pretty(value: &PtrRef) -> Result<String> {
+    stringify_ptr(value.pointer(), true)
+}
+
+fn stringify_ptr(ptr: i32, pretty: bool) -> Result<String> {
+    let string_ptr: i32 = unsafe { json_stringify(ptr, pretty) };
+    match string_ptr {
+        -1 => Err(MochiError::JsonStringifyError),
+        _ => PtrRef::new(string_ptr).as_string()
+    }
+}
+
+impl PtrRef {
+    /// See [stringify].
+    pub fn to_json_string(&self) -> Result<String> {
+        stringify_ptr(self.pointer(), false)
+    }
+
+    /// See [stringify_pretty].
+    pub fn to_json_string_pretty(&self) -> Result<String> {
+        stringify_ptr(self.pointer(), true)
+    }
+}
+
+impl ObjectRef {
+    /// See [stringify].
+    pub fn to_json_string(&self) -> Result<String> {
+        stringify_ptr(self.ptr(), false)
+    }
+
+    /// See [stringify_pretty].
+    pub fn to_json_string_pretty(&self) -> Result<String> {
+        stringify_ptr(self.ptr(), true)
+    }
+}
+
+impl ArrayRef {
+    /// See [stringify].
+    pub fn to_json_string(&self) -> Result<String> {
+        stringify_ptr(self.ptr(), false)
+    }
+
+    /// See [stringify_pretty].
+    pub fn to_json_string_pretty(&self) -> Result<String> {
+        stringify_ptr(self.ptr(), true)
+    }
+}
@@ -0,0 +1,76 @@
+//! Leveled messages for the Mochi console, usually written with the
+//! [debug!](crate::debug), [info!](crate::info), [warn!](crate::warn) and
+//! [error!](crate::error) macros.
+//!
+//! With the `log` feature, [init] also sends the records of the `log` crate
+//! there, including those of other crates.
+
+#[cfg(not(feature = "test-host"))]
+// Not in `env` like `print`, where `log` is the libm function.
+#[link(wasm_import_module = "core")]
+extern "C" {
+    #[link_name = "log"]
+    fn host_log(level: i32, string: *const u8, size: usize);
+}
+
+#[cfg(feature = "test-host")]
+use mochi_test::host::core::log as host_log;
+
+#[repr(i32)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Level {
+    Debug = 0,
+    Info = 1,
+    Warn = 2,
+    Error = 3,
+}
+
+/// Sends a message to the console at `level`.
+pub fn log<T: AsRef<str>>(level: Level, message: T) {
+    let message = message.as_ref();
+    unsafe {
+        host_log(level as i32, message.as_ptr(), message.len());
+    }
+}
+
+#[cfg(feature = "log")]
+mod backend {
+    extern crate alloc;
+
+    use alloc::format;
+
+    use super::Level;
+
+    struct Logger;
+
+    static LOGGER: Logger = Logger;
+
+    impl ::log::Log for Logger {
+        fn enabled(&self, _: &::log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &::log::Record<'_>) {
+            let level = match record.level() {
+                ::log::Level::Trace | ::log::Level::Debug => Level::Debug,
+                ::log::Level::Info => Level::Info,
+                ::log::Level::Warn => Level::Warn,
+                ::log::Level::Error => Level::Error,
+            };
+            super::log(level, format!("[{}] {}", record.target(), record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Sends the records of the `log` crate up to `max_level` to the
+    /// console. Only the first call has an effect.
+    pub fn init(max_level: ::log::LevelFilter) {
+        if ::log::set_logger(&LOGGER).is_ok() {
+            ::log::set_max_level(max_level);
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+pub use backend::init;
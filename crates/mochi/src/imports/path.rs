@@ -0,0 +1,185 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::core::{ArrayRef, Kind, PtrRef};
+use super::error::Result;
+
+enum Step {
+    Key(String),
+    Index(i32),
+    Wildcard,
+}
+
+/// Parses `data.items[*].file`, `results[0]["a.b"]` or `data.*.id`.
+fn parse_path(path: &str) -> Option<Vec<Step>> {
+    let mut steps = Vec::new();
+    let mut key = String::new();
+    // A `.` has to follow a key or a bracket.
+    let mut can_separate = false;
+    let mut chars = path.chars();
+
+    fn push_key(key: &mut String, steps: &mut Vec<Step>) {
+        if !key.is_empty() {
+            let key = core::mem::take(key);
+            steps.push(if key == "*" { Step::Wildcard } else { Step::Key(key) });
+        }
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if !can_separate && key.is_empty() {
+                    return None;
+                }
+                push_key(&mut key, &mut steps);
+                can_separate = false;
+            }
+            '[' => {
+                push_key(&mut key, &mut steps);
+                let mut inner = String::new();
+                loop {
+                    match chars.next()? {
+                        ']' => break,
+                        c => inner.push(c),
+                    }
+                }
+                let inner = inner.trim();
+                let quoted = (inner.starts_with('"') && inner.ends_with('"'))
+                    || (inner.starts_with('\'') && inner.ends_with('\''));
+                steps.push(if inner == "*" {
+                    Step::Wildcard
+                } else if quoted && inner.len() >= 2 {
+                    Step::Key(String::from(&inner[1..inner.len() - 1]))
+                } else {
+                    Step::Index(inner.parse().ok()?)
+                });
+                can_separate = true;
+            }
+            c => {
+                key.push(c);
+                can_separate = true;
+            }
+        }
+    }
+    if !can_separate && !steps.is_empty() {
+        return None;
+    }
+    push_key(&mut key, &mut steps);
+    Some(steps)
+}
+
+fn apply(value: &PtrRef, step: &Step, out: &mut Vec<PtrRef>) {
+    let child = match step {
+        Step::Key(key) => value.child(key),
+        Step::Index(index) if *index < 0 => value.item(value.items_len() + index),
+        Step::Index(index) => value.item(*index),
+        Step::Wildcard => {
+            out.extend(value.children());
+            return;
+        }
+    };
+    if child.is_some() {
+        out.push(child);
+    }
+}
+
+impl PtrRef {
+    /// Looks up a value with a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901),
+    /// e.g. `/data/Media/episodes/0/id`. Returns a null pointer if there's
+    /// no such value.
+    pub fn json_pointer(&self, pointer: &str) -> PtrRef {
+        if pointer.is_empty() {
+            return self.clone();
+        }
+        let Some(pointer) = pointer.strip_prefix('/') else {
+            return PtrRef::null();
+        };
+
+        let mut current: Option<PtrRef> = None;
+        for token in pointer.split('/') {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            let value = current.as_ref().unwrap_or(self);
+            let next = match value.kind() {
+                Kind::Object => value.child(&token),
+                Kind::Array => {
+                    let leading_zero = token.len() > 1 && token.starts_with('0');
+                    match token.parse::<i32>() {
+                        Ok(index) if index >= 0 && !leading_zero && !token.starts_with('+') => {
+                            value.item(index)
+                        }
+                        _ => PtrRef::null(),
+                    }
+                }
+                _ => PtrRef::null(),
+            };
+            if next.is_none() {
+                return PtrRef::null();
+            }
+            current = Some(next);
+        }
+        current.unwrap_or_else(|| self.clone())
+    }
+
+    pub fn json_pointer_str(&self, pointer: &str) -> Result<String> {
+        self.json_pointer(pointer).as_string()
+    }
+
+    pub fn json_pointer_i64(&self, pointer: &str) -> Result<i64> {
+        self.json_pointer(pointer).as_int()
+    }
+
+    pub fn json_pointer_f64(&self, pointer: &str) -> Result<f64> {
+        self.json_pointer(pointer).as_float()
+    }
+
+    pub fn json_pointer_bool(&self, pointer: &str) -> Result<bool> {
+        self.json_pointer(pointer).as_bool()
+    }
+
+    /// Looks up a value with a dotted path such as `data.items[0].file`.
+    /// Keys containing dots can be quoted, as in `headers["x.y"]`, and
+    /// negative indices count from the end.
+    ///
+    /// `*` or `[*]` match every value of an object or array, in which case
+    /// the result is an array of every match, like [path_all](Self::path_all).
+    /// Otherwise a null pointer is returned if there's no such value, or if
+    /// the path is malformed.
+    pub fn path(&self, path: &str) -> PtrRef {
+        let Some(steps) = parse_path(path) else {
+            return PtrRef::null();
+        };
+        if steps.iter().any(|step| matches!(step, Step::Wildcard)) {
+            self.path_all(path).into()
+        } else {
+            self.walk(&steps).into_iter().next().unwrap_or_else(PtrRef::null)
+        }
+    }
+
+    /// Every value matching a [path](Self::path), e.g. each `file` of
+    /// `data.items[*].file`. Values missing from some items are skipped.
+    pub fn path_all(&self, path: &str) -> ArrayRef {
+        match parse_path(path) {
+            Some(steps) => self.walk(&steps).into_iter().collect(),
+            None => ArrayRef::new(),
+        }
+    }
+
+    fn walk(&self, steps: &[Step]) -> Vec<PtrRef> {
+        let Some((first, rest)) = steps.split_first() else {
+            return vec![self.clone()];
+        };
+        let mut current = Vec::new();
+        apply(self, first, &mut current);
+        for step in rest {
+            let mut next = Vec::new();
+            for value in &current {
+                apply(value, step, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+}
@@ -0,0 +1,83 @@
+//! Conversions between host values and any type implementing serde's
+//! `Serialize`/`Deserialize`, enabled with the `serde` feature.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct Sources {
+//!     sources: String,
+//!     encrypted: bool,
+//! }
+//!
+//! let json = Request::new(&url, RequestMethod::Get).json()?;
+//! let sources: Sources = serde::from_ptr(json)?;
+//! ```
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+
+use ::serde::{de::DeserializeOwned, Serialize};
+
+use super::core::PtrRef;
+use super::error::MochiError;
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+/// Deserialize a `T` from a host value, such as a parsed
+/// [JsonValue](crate::std::json::JsonValue).
+pub fn from_ptr<T: DeserializeOwned>(ptr: PtrRef) -> Result<T, Error> {
+    T::deserialize(Deserializer::new(ptr))
+}
+
+/// Serialize a value into a new host value.
+pub fn to_ptr<T: Serialize + ?Sized>(value: &T) -> Result<PtrRef, Error> {
+    value.serialize(Serializer)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Mochi(MochiError),
+    Message(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Mochi(error) => write!(f, "{}", error),
+            Error::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl ::serde::de::StdError for Error {}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl From<MochiError> for Error {
+    fn from(error: MochiError) -> Self {
+        Error::Mochi(error)
+    }
+}
+
+impl From<Error> for MochiError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Mochi(error) => error,
+            Error::Message(message) => MochiError::Custom(message),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+extern crate alloc;
+
+use alloc::string::String;
+
+use ::serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use ::serde::forward_to_deserialize_any;
+
+use super::Error;
+use crate::std::{ArrayRef, Kind, ObjectRef, PtrRef};
+
+/// Deserializes from a host value. Numbers without a fractional part are
+/// visited as integers, the rest as floats.
+pub struct Deserializer {
+    ptr: PtrRef,
+}
+
+impl Deserializer {
+    pub fn new(ptr: PtrRef) -> Self {
+        Self { ptr }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.ptr.kind() {
+            Kind::Null => visitor.visit_unit(),
+            Kind::Bool => visitor.visit_bool(self.ptr.as_bool()?),
+            Kind::Number => {
+                let float = self.ptr.as_float()?;
+                if float == (float as i64) as f64 {
+                    visitor.visit_i64(self.ptr.as_int()?)
+                } else {
+                    visitor.visit_f64(float)
+                }
+            }
+            Kind::String => visitor.visit_string(self.ptr.as_string()?),
+            Kind::Array => visitor.visit_seq(SeqDeserializer {
+                array: self.ptr.as_array()?,
+            }),
+            Kind::Object => visitor.visit_map(MapDeserializer::new(self.ptr.as_object()?)),
+            Kind::Node => Err(de::Error::invalid_type(Unexpected::Other("html node"), &visitor)),
+            Kind::Unknown => Err(de::Error::invalid_type(Unexpected::Other("unknown value"), &visitor)),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.ptr.kind() {
+            Kind::Number => visitor.visit_f64(self.ptr.as_float()?),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.ptr.is_none() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are strings, the others objects with a single key.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.ptr.kind() {
+            Kind::String => visitor.visit_enum(self.ptr.as_string()?.into_deserializer()),
+            Kind::Object => {
+                let object = self.ptr.as_object()?;
+                let mut keys = object.keys();
+                match (keys.next(), keys.next()) {
+                    (Some(key), None) => {
+                        let variant = key.as_string()?;
+                        let value = object.get(&variant);
+                        visitor.visit_enum(EnumDeserializer { variant, value })
+                    }
+                    _ => Err(de::Error::invalid_value(
+                        Unexpected::Map,
+                        &"an object with a single key",
+                    )),
+                }
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqDeserializer {
+    array: ArrayRef,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.array.next() {
+            Some(item) => seed.deserialize(Deserializer::new(item)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapDeserializer {
+    object: ObjectRef,
+    keys: ArrayRef,
+    value: Option<PtrRef>,
+}
+
+impl MapDeserializer {
+    fn new(object: ObjectRef) -> Self {
+        let keys = object.keys();
+        Self { object, keys, value: None }
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        match self.keys.next() {
+            Some(key) => {
+                let key = key.as_string()?;
+                self.value = Some(self.object.get(&key));
+                seed.deserialize(KeyDeserializer { key }).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self.value.take().unwrap_or_else(PtrRef::null);
+        seed.deserialize(Deserializer::new(value))
+    }
+}
+
+/// Object keys are strings, numbers and bools are parsed back from them, as
+/// the [Serializer](super::Serializer) writes them.
+struct KeyDeserializer {
+    key: String,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.key.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(&self.key), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.key)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(self.key))
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: PtrRef,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Deserializer), Error> {
+        let variant = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, Deserializer::new(self.value)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Deserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
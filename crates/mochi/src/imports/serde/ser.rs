@@ -0,0 +1,462 @@
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+
+use ::serde::ser::{self, Impossible, Serialize};
+
+use super::Error;
+use crate::std::{ArrayRef, ObjectRef, PtrRef};
+
+/// Serializes into host values: objects, arrays, strings, numbers, bools
+/// and nulls. Enums use the same representation as the
+/// [Deserializer](super::Deserializer).
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeVariant<SerializeObject>;
+
+    fn serialize_bool(self, v: bool) -> Result<PtrRef, Error> {
+        Ok(PtrRef::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<PtrRef, Error> {
+        Ok(PtrRef::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<PtrRef, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    /// Values above `i64::MAX` lose precision, as the host stores them as floats.
+    fn serialize_u64(self, v: u64) -> Result<PtrRef, Error> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => self.serialize_f64(v as f64),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<PtrRef, Error> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<PtrRef, Error> {
+        Ok(PtrRef::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<PtrRef, Error> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<PtrRef, Error> {
+        Ok(PtrRef::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<PtrRef, Error> {
+        Ok(v.iter().map(|byte| PtrRef::from(*byte as i64)).collect::<ArrayRef>().into())
+    }
+
+    fn serialize_none(self) -> Result<PtrRef, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<PtrRef, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<PtrRef, Error> {
+        Ok(PtrRef::null_value())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<PtrRef, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<PtrRef, Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<PtrRef, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<PtrRef, Error> {
+        let mut object = ObjectRef::new();
+        object.set(variant, value.serialize(Serializer)?);
+        Ok(object.into())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray { array: ArrayRef::new() })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, Error> {
+        Ok(SerializeVariant { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject { object: ObjectRef::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeObject>, Error> {
+        Ok(SerializeVariant { variant, inner: self.serialize_map(Some(len))? })
+    }
+}
+
+pub struct SerializeArray {
+    array: ArrayRef,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.array.insert(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        Ok(self.array.into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub struct SerializeObject {
+    object: ObjectRef,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().unwrap_or_default();
+        self.object.set(&key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        Ok(self.object.into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.object.set(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        Ok(self.object.into())
+    }
+}
+
+/// Wraps a tuple or struct variant in an object keyed by the variant name.
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl SerializeVariant<SerializeArray> {
+    fn wrap(variant: &str, value: PtrRef) -> Result<PtrRef, Error> {
+        let mut object = ObjectRef::new();
+        object.set(variant, value);
+        Ok(object.into())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Self::wrap(self.variant, value)
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject> {
+    type Ok = PtrRef;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<PtrRef, Error> {
+        let value = ser::SerializeStruct::end(self.inner)?;
+        SerializeVariant::<SerializeArray>::wrap(self.variant, value)
+    }
+}
+
+/// Object keys have to be strings; numbers and chars are converted.
+struct KeySerializer;
+
+impl KeySerializer {
+    fn unsupported() -> Error {
+        Error::Message("object keys must be strings or numbers".to_string())
+    }
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Self::unsupported())
+    }
+}
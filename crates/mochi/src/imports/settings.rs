@@ -0,0 +1,43 @@
+extern crate alloc;
+
+use alloc::string::String;
+
+use super::core::PtrRef;
+
+#[cfg(not(feature = "test-host"))]
+#[link(wasm_import_module = "settings")]
+extern "C" {
+    fn get(id_ptr: *const u8, id_len: i32) -> i32;
+}
+
+#[cfg(feature = "test-host")]
+use mochi_test::host::settings::*;
+
+/// The value the user picked for the setting `id`, or a null pointer if
+/// the host doesn't know it.
+///
+/// Settings are described with [SettingsSchema](crate::structs::settings::SettingsSchema),
+/// which also falls back to their defaults.
+pub fn value(id: &str) -> PtrRef {
+    PtrRef::new(unsafe { get(id.as_ptr(), id.len() as i32) })
+}
+
+/// The value of a toggle.
+pub fn toggle(id: &str) -> Option<bool> {
+    value(id).as_bool().ok()
+}
+
+/// The id of the option picked for a select.
+pub fn select(id: &str) -> Option<String> {
+    value(id).as_string().ok()
+}
+
+/// The value of a text field.
+pub fn text(id: &str) -> Option<String> {
+    value(id).as_string().ok()
+}
+
+/// The value of a number field.
+pub fn number(id: &str) -> Option<f64> {
+    value(id).as_float().ok()
+}
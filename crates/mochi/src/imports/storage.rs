@@ -0,0 +1,204 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use super::core::PtrRef;
+
+#[cfg(not(feature = "test-host"))]
+#[link(wasm_import_module = "storage")]
+extern "C" {
+    fn get(key_ptr: *const u8, key_len: i32) -> i32;
+    fn set(key_ptr: *const u8, key_len: i32, value_ptr: i32);
+    fn get_data_len(key_ptr: *const u8, key_len: i32) -> i32;
+    fn get_data(key_ptr: *const u8, key_len: i32, buf_ptr: *mut u8, buf_len: i32);
+    fn set_data(key_ptr: *const u8, key_len: i32, data_ptr: *const u8, data_len: i32);
+    fn remove(key_ptr: *const u8, key_len: i32);
+    fn keys() -> i32;
+}
+
+#[cfg(feature = "test-host")]
+use mochi_test::host::storage::*;
+
+/// Values kept on the host between runs of the source, such as a login token
+/// or a key that is expensive to fetch.
+///
+/// Every source has its own storage, so keys never clash with another
+/// source's. Within a source, [scoped](Self::scoped) storages split it up
+/// further by prefixing their keys.
+///
+/// Values are anything that converts to and from a [PtrRef]:
+///
+/// ```ignore
+/// let storage = Storage::new();
+/// storage.set("token", "abc");
+/// let token: Option<String> = storage.get("token");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Storage {
+    prefix: String,
+}
+
+impl Storage {
+    /// The whole storage of the source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Storage whose keys are prefixed with `name/`.
+    pub fn scoped<T: AsRef<str>>(&self, name: T) -> Self {
+        Self {
+            prefix: format!("{}{}/", self.prefix, name.as_ref()),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// The stored value, or a null pointer if there's none.
+    pub fn get_ptr(&self, key: &str) -> PtrRef {
+        let key = self.key(key);
+        PtrRef::new(unsafe { get(key.as_ptr(), key.len() as i32) })
+    }
+
+    pub fn get<T>(&self, key: &str) -> Option<T>
+    where
+        PtrRef: Into<T>,
+    {
+        let value = self.get_ptr(key);
+        if value.is_none() {
+            None
+        } else {
+            Some(value.into())
+        }
+    }
+
+    /// Stores a copy of `value`, replacing the previous one.
+    pub fn set<T: Into<PtrRef>>(&self, key: &str, value: T) {
+        let key = self.key(key);
+        let value: PtrRef = value.into();
+        unsafe { set(key.as_ptr(), key.len() as i32, value.pointer()) };
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        self.get_ptr(key).as_string().ok()
+    }
+
+    pub fn set_string(&self, key: &str, value: &str) {
+        self.set(key, value);
+    }
+
+    /// Bytes stored with [set_bytes](Self::set_bytes). Strings are returned
+    /// as their UTF-8 bytes.
+    pub fn get_bytes(&self, key: &str) -> Option<Vec<u8>> {
+        let key = self.key(key);
+        let len = unsafe { get_data_len(key.as_ptr(), key.len() as i32) };
+        if len < 0 {
+            return None;
+        }
+        let mut data = vec![0u8; len as usize];
+        unsafe { get_data(key.as_ptr(), key.len() as i32, data.as_mut_ptr(), len) };
+        Some(data)
+    }
+
+    pub fn set_bytes(&self, key: &str, value: &[u8]) {
+        let key = self.key(key);
+        unsafe { set_data(key.as_ptr(), key.len() as i32, value.as_ptr(), value.len() as i32) };
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.get_ptr(key).is_some()
+    }
+
+    pub fn remove(&self, key: &str) {
+        let key = self.key(key);
+        unsafe { remove(key.as_ptr(), key.len() as i32) };
+    }
+
+    /// Keys of this storage in order, without its prefix.
+    pub fn keys(&self) -> Vec<String> {
+        let keys: Vec<String> = PtrRef::new(unsafe { keys() }).into();
+        keys.into_iter()
+            .filter_map(|key| key.strip_prefix(self.prefix.as_str()).map(String::from))
+            .collect()
+    }
+
+    /// Removes every key of this storage.
+    pub fn clear(&self) {
+        for key in self.keys() {
+            self.remove(&key);
+        }
+    }
+}
+
+/// A key with the type of its value, so a value is stored and read back the
+/// same way everywhere.
+///
+/// ```ignore
+/// const TOKEN: Key<String> = Key::new("token");
+///
+/// TOKEN.set(token);
+/// let token = TOKEN.get();
+/// ```
+#[derive(Debug)]
+pub struct Key<T> {
+    name: &'static str,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> Key<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            value: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get(&self) -> Option<T>
+    where
+        PtrRef: Into<T>,
+    {
+        Storage::new().get(self.name)
+    }
+
+    pub fn get_in(&self, storage: &Storage) -> Option<T>
+    where
+        PtrRef: Into<T>,
+    {
+        storage.get(self.name)
+    }
+
+    pub fn set(&self, value: T)
+    where
+        T: Into<PtrRef>,
+    {
+        Storage::new().set(self.name, value)
+    }
+
+    pub fn set_in(&self, storage: &Storage, value: T)
+    where
+        T: Into<PtrRef>,
+    {
+        storage.set(self.name, value)
+    }
+
+    pub fn remove(&self) {
+        Storage::new().remove(self.name)
+    }
+}
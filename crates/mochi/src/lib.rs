@@ -1,14 +1,17 @@
 #![no_std]
-#![feature(
-    core_intrinsics,
-    alloc_error_handler,
-    fmt_internals,
-    panic_info_message
-)]
-
-#[cfg_attr(feature = "dlmalloc", global_allocator)]
+// `Into<T> for PtrRef` is how host values are read into rust types.
+#![allow(clippy::from_over_into)]
+// Import modules only namespace the host imports on wasm, elsewhere the
+// extern declarations share one namespace and some names repeat.
+#![cfg_attr(not(target_arch = "wasm32"), allow(clashing_extern_declarations))]
+
+// Native builds with `test-host` link the host imports against `mochi-test`
+// and run inside a std binary, which provides the allocator and panic handler.
+#[cfg(all(feature = "dlmalloc", target_arch = "wasm32"))]
+#[global_allocator]
 static ALLOCATOR: dlmalloc::GlobalDlmalloc = dlmalloc::GlobalDlmalloc;
 
+#[cfg(target_arch = "wasm32")]
 fn as_abort<T: AsRef<str>>(message: T, file: T, line: u32, column: u32) -> ! {
     extern "C" {
         #[link_name = "abort"]
@@ -33,7 +36,7 @@ fn as_abort<T: AsRef<str>>(message: T, file: T, line: u32, column: u32) -> ! {
             let message_ptr = message_len_ptr.add(1) as *mut u8;
             copy::<u8>(message.as_ptr(), message_ptr, message.len());
 
-            let file_len_ptr = message_len_ptr.add(message.len()) as *mut i32;
+            let file_len_ptr = message_len_ptr.add(message.len());
             *file_len_ptr = i32::try_from(file.len()).unwrap_or(-1);
 
             let file_ptr = file_len_ptr.add(1) as *mut u8;
@@ -53,9 +56,10 @@ fn as_abort<T: AsRef<str>>(message: T, file: T, line: u32, column: u32) -> ! {
         }
     }
 
-    core::intrinsics::abort()
+    core::arch::wasm32::unreachable()
 }
 
+#[cfg(target_arch = "wasm32")]
 #[cfg_attr(not(test), panic_handler)]
 pub fn panic_handle(info: &core::panic::PanicInfo) -> ! {
     // use crate::imports::Write;
@@ -69,13 +73,8 @@ pub fn panic_handle(info: &core::panic::PanicInfo) -> ! {
         ("", 0, 0)
     };
 
-    let message = if let Some(args) = info.message() {
-        let mut string = String::with_capacity(args.estimated_capacity());
-        string.write_fmt(*args).unwrap_or_default();
-        string
-    } else {
-        String::new()
-    };
+    let mut message = String::new();
+    write!(message, "{}", info.message()).unwrap_or_default();
 
     as_abort(message, String::from(file), line, col)
 }
@@ -92,7 +91,13 @@ pub mod std {
 
 pub use mochi_bind::*;
 
+#[doc(hidden)]
+pub mod abi;
+
 pub mod structs;
 
 #[cfg(feature = "extractors")]
-pub mod extractors;
\ No newline at end of file
+pub mod extractors;
+
+#[cfg(feature = "test-host")]
+pub mod test;
\ No newline at end of file
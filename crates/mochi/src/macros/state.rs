@@ -0,0 +1,31 @@
+/// Declares a function giving access to state that is kept between calls into
+/// the source. Natively, each thread has its own, like the emulated host.
+///
+/// ```ignore
+/// global_state! {
+///     fn with_state() -> State = State::new();
+/// }
+/// ```
+macro_rules! global_state {
+    ($vis:vis fn $name:ident() -> $ty:ty = $init:expr;) => {
+        #[cfg(target_arch = "wasm32")]
+        $vis fn $name<R>(f: impl FnOnce(&mut $ty) -> R) -> R {
+            struct Global(core::cell::RefCell<$ty>);
+            // Sources run on a single thread.
+            unsafe impl Sync for Global {}
+            static STATE: Global = Global(core::cell::RefCell::new($init));
+            f(&mut STATE.0.borrow_mut())
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        $vis fn $name<R>(f: impl FnOnce(&mut $ty) -> R) -> R {
+            extern crate std;
+            std::thread_local! {
+                static STATE: core::cell::RefCell<$ty> = const { core::cell::RefCell::new($init) };
+            }
+            STATE.with(|state| f(&mut state.borrow_mut()))
+        }
+    };
+}
+
+pub(crate) use global_state;
@@ -1,17 +1,92 @@
+extern crate alloc;
+
+use alloc::string::String;
+use core::fmt;
+
+// Called by the macros below, which can't name `alloc` in downstream crates.
+#[doc(hidden)]
+pub fn format(args: fmt::Arguments<'_>) -> String {
+    alloc::fmt::format(args)
+}
+
+/// `format!` for sources that don't declare `extern crate alloc`.
 #[macro_export]
 macro_rules! format {
     ($($arg:tt)*) => {{
-        crate::imports::core::format(core::format_args!($($arg)*))
+        $crate::utils::format(::core::format_args!($($arg)*))
+    }};
+}
+
+/// Prints to the Mochi console. Each call is a line of its own, so this is
+/// the same as [println!](crate::println).
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {{
+        $crate::std::print($crate::utils::format(::core::format_args!($($arg)*)));
     }};
 }
 
+/// Prints a line to the Mochi console.
 #[macro_export]
 macro_rules! println {
     () => {{
-        crate::imports::core::print("");
+        $crate::std::print("");
     }};
     ($($arg:tt)*) => {{
-        let string = crate::imports::core::format(core::format_args!($($arg)*));
-        crate::import::core::print(&(string));
+        $crate::std::print($crate::utils::format(::core::format_args!($($arg)*)));
     }};
-}
\ No newline at end of file
+}
+
+/// Prints a line to the Mochi console as an error.
+#[macro_export]
+macro_rules! eprintln {
+    () => {{
+        $crate::std::log::log($crate::std::log::Level::Error, "");
+    }};
+    ($($arg:tt)*) => {{
+        $crate::std::log::log(
+            $crate::std::log::Level::Error,
+            $crate::utils::format(::core::format_args!($($arg)*)),
+        );
+    }};
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        $crate::std::log::log(
+            $crate::std::log::Level::Debug,
+            $crate::utils::format(::core::format_args!($($arg)*)),
+        );
+    }};
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {{
+        $crate::std::log::log(
+            $crate::std::log::Level::Info,
+            $crate::utils::format(::core::format_args!($($arg)*)),
+        );
+    }};
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        $crate::std::log::log(
+            $crate::std::log::Level::Warn,
+            $crate::utils::format(::core::format_args!($($arg)*)),
+        );
+    }};
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {{
+        $crate::std::log::log(
+            $crate::std::log::Level::Error,
+            $crate::utils::format(::core::format_args!($($arg)*)),
+        );
+    }};
+}
@@ -1,5 +1,7 @@
 mod conversion;
 pub mod image;
 pub mod meta;
+pub mod settings;
+pub mod source;
 pub mod text;
 pub mod video;
@@ -3,7 +3,7 @@ use alloc::string::String;
 
 // Useful for only trying to return a value
 // without dropping the string if it's not None.
-pub(crate) fn optional_str_ptr(value: Option<String>) -> (i32, i32) {
+pub(crate) fn optional_str_ptr(value: Option<String>) -> (*const u8, i32) {
     match value {
         Some(string) => {
             let str_ptr = string.as_ptr();
@@ -11,8 +11,8 @@ pub(crate) fn optional_str_ptr(value: Option<String>) -> (i32, i32) {
             // Need to forget this string since it gets dropped
             // since it get's out of scope.
             core::mem::forget(string);
-            (str_ptr as i32, str_len as i32)
+            (str_ptr, str_len as i32)
         },
-        _ => (-1, -1),
+        _ => (-1isize as *const u8, -1),
     }
 }
\ No newline at end of file
@@ -9,19 +9,20 @@ use crate::imports::{PtrRef, ArrayRef};
 
 use super::conversion::optional_str_ptr;
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "structs_meta")]
 extern "C" {
     fn create_search_filter_option(
-        option_id_ptr: i32,
+        option_id_ptr: *const u8,
         option_id_len: i32,
-        name_ptr: i32, 
+        name_ptr: *const u8, 
         name_len: i32
     ) -> i32;
 
     fn create_search_filter(
-        id_ptr: i32, 
+        id_ptr: *const u8, 
         id_len: i32, 
-        name_ptr: i32, 
+        name_ptr: *const u8, 
         name_len: i32, 
         options_arr_ref: i32,
         multiselect: bool,
@@ -29,36 +30,36 @@ extern "C" {
     ) -> i32;
 
     fn create_paging(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        previous_page_ptr: i32,
+        previous_page_ptr: *const u8,
         previous_page_len: i32,
-        next_page_ptr: i32,
+        next_page_ptr: *const u8,
         next_page_len: i32,
         items_ptr: i32
     ) -> i32;
 
     fn create_discover_listing(
-        title_ptr: i32,
+        title_ptr: *const u8,
         title_len: i32,
         lising_type: i32,
         paging_ptr: i32
     ) -> i32;
 
     fn create_playlist(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        title_ptr: i32,
+        title_ptr: *const u8,
         title_len: i32,
-        poster_image_ptr: i32,
+        poster_image_ptr: *const u8,
         poster_image_len: i32,
-        banner_image_ptr: i32,
+        banner_image_ptr: *const u8,
         banner_image_len: i32,
         playlist_type: PlaylistType
     ) -> i32;
 
     fn create_playlist_details(
-        description_ptr: i32,
+        description_ptr: *const u8,
         description_len: i32,
         alternative_titles_ptr: i32,
         alternative_posters_ptr: i32,
@@ -70,28 +71,28 @@ extern "C" {
     ) -> i32;
 
     fn create_playlist_preview(
-        title_ptr: i32,
+        title_ptr: *const u8,
         title_len: i32,
-        description_ptr: i32,
+        description_ptr: *const u8,
         description_len: i32,
-        thumbnail_ptr: i32,
+        thumbnail_ptr: *const u8,
         thumbnail_len: i32,
-        link_ptr: i32,
+        link_ptr: *const u8,
         link_len: i32,
         preview_type: PlaylistPreviewType
     ) -> i32;
 
     fn create_playlist_item(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        title_ptr: i32,
+        title_ptr: *const u8,
         title_len: i32,
-        description_ptr: i32,
+        description_ptr: *const u8,
         description_len: i32,
-        thumbnail_ptr: i32,
+        thumbnail_ptr: *const u8,
         thumbnail_len: i32,
         number: f64,
-        timestamp_ptr: i32,
+        timestamp_ptr: *const u8,
         timestamp_len: i32,
         tags_ptr: i32
     ) -> i32;
@@ -103,14 +104,14 @@ extern "C" {
 
     fn create_playlist_group(
         id: f64,
-        display_title_ptr: i32,
+        display_title_ptr: *const u8,
         display_title_len: i32
     ) -> i32;
 
     fn create_playlist_group_page(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        display_name_ptr: i32,
+        display_name_ptr: *const u8,
         display_name_len: i32
     ) -> i32;
 
@@ -121,6 +122,61 @@ extern "C" {
     ) -> i32;
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::structs_meta::*;
+
+#[cfg(feature = "test-host")]
+#[allow(clippy::too_many_arguments)]
+unsafe fn create_playlist(
+    id_ptr: *const u8,
+    id_len: i32,
+    title_ptr: *const u8,
+    title_len: i32,
+    poster_image_ptr: *const u8,
+    poster_image_len: i32,
+    banner_image_ptr: *const u8,
+    banner_image_len: i32,
+    playlist_type: PlaylistType
+) -> i32 {
+    mochi_test::host::structs_meta::create_playlist(
+        id_ptr,
+        id_len,
+        title_ptr,
+        title_len,
+        poster_image_ptr,
+        poster_image_len,
+        banner_image_ptr,
+        banner_image_len,
+        playlist_type as i32
+    )
+}
+
+#[cfg(feature = "test-host")]
+#[allow(clippy::too_many_arguments)]
+unsafe fn create_playlist_preview(
+    title_ptr: *const u8,
+    title_len: i32,
+    description_ptr: *const u8,
+    description_len: i32,
+    thumbnail_ptr: *const u8,
+    thumbnail_len: i32,
+    link_ptr: *const u8,
+    link_len: i32,
+    preview_type: PlaylistPreviewType
+) -> i32 {
+    mochi_test::host::structs_meta::create_playlist_preview(
+        title_ptr,
+        title_len,
+        description_ptr,
+        description_len,
+        thumbnail_ptr,
+        thumbnail_len,
+        link_ptr,
+        link_len,
+        preview_type as i32
+    )
+}
+
 pub trait Meta {
     fn search_filters() -> SearchFilters;
     fn search(search_query: SearchQuery) -> Result<Paging<Playlist>>;
@@ -318,9 +374,9 @@ impl From<SearchFilter> for PtrRef {
 
         let ptr = unsafe {
             create_search_filter(
-                value.filter_id.as_ptr() as i32,
+                value.filter_id.as_ptr(),
                 value.filter_id.len() as i32,
-                value.display_name.as_ptr() as i32,
+                value.display_name.as_ptr(),
                 value.display_name.len() as i32,
                 options_array_ref,
                 value.multiselect,
@@ -336,9 +392,9 @@ impl From<SearchFilterOption> for PtrRef {
         Self::new(
             unsafe {
                 create_search_filter_option(
-                    value.option_id.as_ptr() as i32,
+                    value.option_id.as_ptr(),
                     value.option_id.len() as i32,
-                    value.display_name.as_ptr() as i32,
+                    value.display_name.as_ptr(),
                     value.display_name.len() as i32
                 )
             }    
@@ -404,7 +460,7 @@ impl<T> From<Paging<T>> for PtrRef where PtrRef: From<T> {
 
         let obj_ptr = unsafe {
             create_paging(
-                value.id.as_ptr() as i32, 
+                value.id.as_ptr(), 
                 value.id.len() as i32, 
                 previous_page.0, 
                 previous_page.1, 
@@ -427,7 +483,7 @@ impl From<DiscoverListing> for PtrRef {
 
         let obj_ptr = unsafe {
             create_discover_listing(
-                title.as_ptr() as i32, 
+                title.as_ptr(), 
                 title.len() as i32, 
                 listing_type as i32,
                 paging_ptr
@@ -455,7 +511,7 @@ impl From<Playlist> for PtrRef {
         let banner_image = optional_str_ptr(value.banner_image);
         let host_ptr = unsafe {
             create_playlist(
-                id.as_ptr() as i32, 
+                id.as_ptr(), 
                 id.len() as i32,
                 title.0,
                 title.1,
@@ -527,12 +583,12 @@ impl From<PlaylistPreview> for PtrRef {
                 description.1, 
                 thumbnail.0, 
                 thumbnail.1,
-                value.link.as_ptr() as i32,
+                value.link.as_ptr(),
                 value.link.len() as i32, 
                 value.preview_type
             )
         };
-        PtrRef::from(host_ptr)
+        Self::new(host_ptr)
     }
 }
 
@@ -549,7 +605,7 @@ impl From<PlaylistItem> for PtrRef {
 
         let host_ptr = unsafe {
             create_playlist_item(
-                value.id.as_ptr() as i32,
+                value.id.as_ptr(),
                 value.id.len() as i32,
                 title.0,
                 title.1,
@@ -563,7 +619,7 @@ impl From<PlaylistItem> for PtrRef {
                 tags_ptr
             )
         };
-        Self::from(host_ptr)
+        Self::new(host_ptr)
     }
 }
 
@@ -629,9 +685,9 @@ impl From<PlaylistGroupPage> for PtrRef {
         Self::new(
             unsafe {
                 create_playlist_group_page(
-                    value.id.as_ptr() as i32,
+                    value.id.as_ptr(),
                     value.id.len() as i32,
-                    value.display_name.as_ptr() as i32,
+                    value.display_name.as_ptr(),
                     value.display_name.len() as i32
                 )
             }
@@ -0,0 +1,236 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::imports::settings;
+use crate::imports::{ArrayRef, ObjectRef, PtrRef};
+
+/// Options the user can change for a source, such as a preferred server or
+/// subtitle language. Exported with `#[mochi_bind]` as `settings`.
+///
+/// ```ignore
+/// #[mochi_bind]
+/// impl Settings for Source {
+///     fn settings() -> SettingsSchema {
+///         SettingsSchema(vec![
+///             Setting::select("server", "Server", [("vidcloud", "VidCloud"), ("upcloud", "UpCloud")], "vidcloud"),
+///             Setting::toggle("dub", "Prefer dub", false),
+///         ])
+///     }
+/// }
+///
+/// let server = Source::settings().select("server");
+/// ```
+pub trait Settings {
+    fn settings() -> SettingsSchema;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsSchema(pub Vec<Setting>);
+
+impl SettingsSchema {
+    pub fn setting(&self, id: &str) -> Option<&Setting> {
+        self.0.iter().find(|setting| setting.id == id)
+    }
+
+    /// The value of the toggle `id`, or its default if the user hasn't
+    /// changed it. `false` if there's no such toggle.
+    pub fn toggle(&self, id: &str) -> bool {
+        settings::toggle(id).unwrap_or_else(|| match self.setting(id).map(|s| &s.kind) {
+            Some(SettingKind::Toggle { default }) => *default,
+            _ => false,
+        })
+    }
+
+    /// The option picked for the select `id`, or its default. Options that
+    /// are no longer in the schema are ignored.
+    pub fn select(&self, id: &str) -> String {
+        let Some(SettingKind::Select { options, default }) = self.setting(id).map(|s| &s.kind) else {
+            return settings::select(id).unwrap_or_default();
+        };
+        settings::select(id)
+            .filter(|value| options.iter().any(|option| &option.id == value))
+            .unwrap_or_else(|| default.clone())
+    }
+
+    /// The value of the text field `id`, or its default.
+    pub fn text(&self, id: &str) -> String {
+        settings::text(id).unwrap_or_else(|| match self.setting(id).map(|s| &s.kind) {
+            Some(SettingKind::Text { default, .. }) => default.clone(),
+            _ => String::new(),
+        })
+    }
+
+    /// The value of the number field `id`, or its default, clamped to its
+    /// bounds.
+    pub fn number(&self, id: &str) -> f64 {
+        match self.setting(id).map(|s| &s.kind) {
+            Some(SettingKind::Number { default, min, max, .. }) => {
+                let mut value = settings::number(id).unwrap_or(*default);
+                if let Some(min) = min {
+                    value = value.max(*min);
+                }
+                if let Some(max) = max {
+                    value = value.min(*max);
+                }
+                value
+            }
+            _ => settings::number(id).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    /// Key the value is read with.
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub kind: SettingKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingKind {
+    Toggle {
+        default: bool,
+    },
+    /// One of `options`, read as the id of the option.
+    Select {
+        options: Vec<SettingOption>,
+        default: String,
+    },
+    Text {
+        default: String,
+        placeholder: Option<String>,
+    },
+    Number {
+        default: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+        step: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingOption {
+    pub id: String,
+    pub display_name: String,
+}
+
+impl Setting {
+    fn new<A: Into<String>, B: Into<String>>(id: A, title: B, kind: SettingKind) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            kind,
+        }
+    }
+
+    pub fn toggle<A: Into<String>, B: Into<String>>(id: A, title: B, default: bool) -> Self {
+        Self::new(id, title, SettingKind::Toggle { default })
+    }
+
+    /// `options` are `(id, display name)` pairs.
+    pub fn select<A, B, C, D, I>(id: A, title: B, options: I, default: &str) -> Self
+    where
+        A: Into<String>,
+        B: Into<String>,
+        C: Into<String>,
+        D: Into<String>,
+        I: IntoIterator<Item = (C, D)>,
+    {
+        let options = options
+            .into_iter()
+            .map(|(id, display_name)| SettingOption {
+                id: id.into(),
+                display_name: display_name.into(),
+            })
+            .collect();
+        Self::new(id, title, SettingKind::Select { options, default: default.into() })
+    }
+
+    pub fn text<A: Into<String>, B: Into<String>>(id: A, title: B, default: &str) -> Self {
+        Self::new(id, title, SettingKind::Text { default: default.into(), placeholder: None })
+    }
+
+    pub fn number<A: Into<String>, B: Into<String>>(id: A, title: B, default: f64) -> Self {
+        Self::new(id, title, SettingKind::Number { default, min: None, max: None, step: None })
+    }
+
+    pub fn description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Placeholder of a text field.
+    pub fn placeholder<T: Into<String>>(mut self, placeholder: T) -> Self {
+        if let SettingKind::Text { placeholder: value, .. } = &mut self.kind {
+            *value = Some(placeholder.into());
+        }
+        self
+    }
+
+    /// Bounds and step of a number field.
+    pub fn range(mut self, min: f64, max: f64, step: f64) -> Self {
+        if let SettingKind::Number { min: a, max: b, step: c, .. } = &mut self.kind {
+            (*a, *b, *c) = (Some(min), Some(max), Some(step));
+        }
+        self
+    }
+}
+
+impl From<SettingsSchema> for PtrRef {
+    fn from(value: SettingsSchema) -> Self {
+        ArrayRef::from(value.0).into()
+    }
+}
+
+impl From<Setting> for PtrRef {
+    fn from(value: Setting) -> Self {
+        let mut object = ObjectRef::new();
+        object.set("id", value.id.into());
+        object.set("title", value.title.into());
+        if let Some(description) = value.description {
+            object.set("description", description.into());
+        }
+        match value.kind {
+            SettingKind::Toggle { default } => {
+                object.set("type", "toggle".into());
+                object.set("default", default.into());
+            }
+            SettingKind::Select { options, default } => {
+                object.set("type", "select".into());
+                object.set("options", options.into());
+                object.set("default", default.into());
+            }
+            SettingKind::Text { default, placeholder } => {
+                object.set("type", "text".into());
+                object.set("default", default.into());
+                if let Some(placeholder) = placeholder {
+                    object.set("placeholder", placeholder.into());
+                }
+            }
+            SettingKind::Number { default, min, max, step } => {
+                object.set("type", "number".into());
+                object.set("default", default.into());
+                for (key, value) in [("min", min), ("max", max), ("step", step)] {
+                    if let Some(value) = value {
+                        object.set(key, value.into());
+                    }
+                }
+            }
+        }
+        object.into()
+    }
+}
+
+impl From<SettingOption> for PtrRef {
+    fn from(value: SettingOption) -> Self {
+        let mut object = ObjectRef::new();
+        object.set("id", value.id.into());
+        object.set("displayName", value.display_name.into());
+        object.into()
+    }
+}
@@ -0,0 +1,133 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::imports::{ObjectRef, PtrRef};
+
+/// Describes a source to the host: what it's called, where it scrapes and
+/// which traits it implements.
+///
+/// Usually generated with `#[mochi_source(...)]`, which exports it as
+/// `source_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    /// Valid URL Image
+    pub icon: Option<String>,
+    /// Valid url string
+    pub base_url: Option<String>,
+    /// Language of the content, e.g. `en`.
+    pub language: Option<String>,
+    pub nsfw: bool,
+    pub capabilities: Vec<Capability>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Meta,
+    Video,
+    Image,
+    Text,
+    Settings,
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::Meta => "meta",
+            Capability::Video => "video",
+            Capability::Image => "image",
+            Capability::Text => "text",
+            Capability::Settings => "settings",
+        }
+    }
+}
+
+impl From<Capability> for PtrRef {
+    fn from(value: Capability) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<SourceInfo> for PtrRef {
+    fn from(value: SourceInfo) -> Self {
+        let mut object = ObjectRef::new();
+        object.set("id", value.id.into());
+        object.set("name", value.name.into());
+        object.set("version", value.version.into());
+        if let Some(icon) = value.icon {
+            object.set("icon", icon.into());
+        }
+        if let Some(base_url) = value.base_url {
+            object.set("baseUrl", base_url.into());
+        }
+        if let Some(language) = value.language {
+            object.set("language", language.into());
+        }
+        object.set("nsfw", value.nsfw.into());
+        object.set("capabilities", value.capabilities.into());
+        object.into()
+    }
+}
+
+/// Finds out which traits a type implements, for `#[mochi_source]`.
+///
+/// Each method resolves to the `Is*` trait when the bound holds, and to
+/// [Fallback] through auto-ref otherwise. Only works on concrete types.
+#[doc(hidden)]
+pub mod probe {
+    use core::marker::PhantomData;
+
+    use super::super::image::Image;
+    use super::super::meta::Meta;
+    use super::super::settings::Settings;
+    use super::super::text::Text;
+    use super::super::video::Video;
+
+    pub struct Probe<T>(PhantomData<T>);
+
+    impl<T> Probe<T> {
+        pub const fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<T> Default for Probe<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    pub trait Fallback {
+        fn meta(&self) -> bool { false }
+        fn video(&self) -> bool { false }
+        fn image(&self) -> bool { false }
+        fn text(&self) -> bool { false }
+        fn settings(&self) -> bool { false }
+    }
+
+    impl<T> Fallback for &Probe<T> {}
+
+    macro_rules! probe {
+        ($($name:ident: $trait:ident => $method:ident),* $(,)?) => {
+            $(
+                pub trait $name {
+                    fn $method(&self) -> bool { true }
+                }
+
+                impl<T: $trait> $name for Probe<T> {}
+            )*
+        };
+    }
+
+    probe! {
+        IsMeta: Meta => meta,
+        IsVideo: Video => video,
+        IsImage: Image => image,
+        IsText: Text => text,
+        IsSettings: Settings => settings,
+    }
+}
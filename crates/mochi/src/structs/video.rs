@@ -8,24 +8,25 @@ use super::conversion::optional_str_ptr;
 
 use super::meta::{PlaylistItemsRequest, PlaylistItemsResponse};
 
+#[cfg(not(feature = "test-host"))]
 #[link(wasm_import_module = "structs_video")]
 extern "C" {
     fn create_episode_source(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        display_name_ptr: i32,
+        display_name_ptr: *const u8,
         display_name_len: i32,
-        description_ptr: i32,
+        description_ptr: *const u8,
         description_len: i32,
         servers_ptr: i32
     ) -> i32;
 
     fn create_episode_server(
-        id_ptr: i32,
+        id_ptr: *const u8,
         id_len: i32,
-        display_name_ptr: i32,
+        display_name_ptr: *const u8,
         display_name_len: i32,
-        description_ptr: i32,
+        description_ptr: *const u8,
         description_len: i32
     ) -> i32;
 
@@ -37,16 +38,16 @@ extern "C" {
     ) -> i32;
 
     fn create_episode_server_link(
-        url_ptr: i32,
+        url_ptr: *const u8,
         url_len: i32,
         quality: i32,
         format: i32
     ) -> i32;
 
     fn create_episode_server_subtitle(
-        url_ptr: i32,
+        url_ptr: *const u8,
         url_len: i32,
-        name_ptr: i32,
+        name_ptr: *const u8,
         name_len: i32,
         format: i32,
         default: bool,
@@ -60,6 +61,22 @@ extern "C" {
     ) -> i32;
 }
 
+#[cfg(feature = "test-host")]
+use mochi_test::host::structs_video::*;
+
+#[cfg(feature = "test-host")]
+unsafe fn create_episode_server_skip_time(
+    start_time: f32,
+    end_time: f32,
+    skip_type: PlaylistEpisodeServerSkipType
+) -> i32 {
+    mochi_test::host::structs_video::create_episode_server_skip_time(
+        start_time,
+        end_time,
+        skip_type as i32
+    )
+}
+
 pub trait Video {
     fn playlist_episodes(request: PlaylistItemsRequest) -> Result<PlaylistItemsResponse>;
     fn playlist_episode_sources(request: PlaylistEpisodeSourcesRequest) -> Result<PlaylistEpisodeSources>;
@@ -252,9 +269,9 @@ impl From<PlaylistEpisodeSource> for PtrRef {
 
         let source_ptr = unsafe {
             create_episode_source(
-                value.id.as_ptr() as i32, 
+                value.id.as_ptr(), 
                 value.id.len() as i32, 
-                value.display_name.as_ptr() as i32, 
+                value.display_name.as_ptr(), 
                 value.display_name.len() as i32, 
                 description.0, 
                 description.1, 
@@ -270,9 +287,9 @@ impl From<PlaylistEpisodeServer> for PtrRef {
         let description = optional_str_ptr(value.description);
         let server_ptr = unsafe {
             create_episode_server(
-                value.id.as_ptr() as i32,
+                value.id.as_ptr(),
                 value.id.len() as i32,
-                value.display_name.as_ptr() as i32,
+                value.display_name.as_ptr(),
                 value.display_name.len() as i32,
                 description.0,
                 description.1
@@ -333,7 +350,7 @@ impl From<PlaylistEpisodeServerLink> for PtrRef {
     fn from(value: PlaylistEpisodeServerLink) -> Self {
         let ptr = unsafe {
             create_episode_server_link(
-                value.url.as_ptr() as i32, 
+                value.url.as_ptr(), 
                 value.url.len() as i32,
                 value.quality.into(),
                 value.format_type as i32
@@ -347,9 +364,9 @@ impl From<PlaylistEpisodeServerSubtitle> for PtrRef {
     fn from(value: PlaylistEpisodeServerSubtitle) -> Self {
         let ptr = unsafe {
             create_episode_server_subtitle(
-                value.url.as_ptr() as i32,
+                value.url.as_ptr(),
                 value.url.len() as i32,
-                value.name.as_ptr() as i32,
+                value.name.as_ptr(),
                 value.name.len() as i32,
                 value.format as i32,
                 value.default,
@@ -0,0 +1,46 @@
+//! Helpers for testing sources natively with `cargo test`, available with the
+//! `test-host` feature.
+//!
+//! ```ignore
+//! use mochi::test::{mochi_test, to_host};
+//!
+//! #[mochi_test]
+//! fn discover_listings_has_trending() {
+//!     let listings = to_host(MySource::discover_listings());
+//!     let first = &listings.as_array().unwrap()[0];
+//!     assert_eq!(first.get("title").and_then(|t| t.as_str()), Some("Trending"));
+//! }
+//! ```
+
+pub use mochi_test::fixtures;
+pub use mochi_test::host;
+pub use mochi_test::server;
+pub use mochi_test::host::Value;
+pub use mochi_test::mochi_test;
+
+use crate::std::PtrRef;
+
+/// Start over with a fresh host for the current thread, also dropping the
+/// state the library keeps between calls, such as cached responses and the
+/// default `RetryPolicy`. `#[mochi_test]` calls it before each test.
+pub fn reset() {
+    host::reset();
+    crate::std::http::reset_state();
+}
+
+/// Convert a value into a host pointer, the same way `#[mochi_bind]` exports
+/// return it to the app, and read back what the host received.
+pub fn to_host<T>(value: T) -> Value where PtrRef: From<T> {
+    let ptr_ref = PtrRef::from(value);
+    let ptr = ptr_ref.pointer();
+    core::mem::forget(ptr_ref);
+    host::take(ptr).unwrap_or(Value::Null)
+}
+
+/// Build a rust value from a host value, the same way `#[mochi_bind]` exports
+/// read their arguments.
+///
+/// Useful to create requests such as `SearchQuery` from a JSON value.
+pub fn from_host<T>(value: Value) -> T where PtrRef: Into<T> {
+    PtrRef::new(host::insert(value)).into()
+}
@@ -0,0 +1,15 @@
+/// Signatures `#[mochi_bind]` rejects and types the derives can't be used on,
+/// with the errors and spans in `tests/ui/*.stderr`. Run with
+/// `TRYBUILD=overwrite` to update them.
+#[test]
+fn rejects_invalid_signatures() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+/// Implementations `#[mochi_bind]` accepts, in `tests/ui/pass`.
+#[test]
+fn accepts_valid_implementations() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,70 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::http::{Multipart, Request, RequestMethod};
+use mochi::std::{ArrayRef, ObjectRef};
+use mochi::test::mochi_test;
+use mochi::test::server::{Received, Route, Server};
+
+/// Sends the request `build` makes to a local server, and returns what the
+/// server received.
+fn send(build: impl FnOnce(Request) -> Request) -> Received {
+    let server = Server::start().unwrap();
+    server.route(Route::post("/submit"));
+    let response = build(Request::new(server.url("/submit"), RequestMethod::Post)).send();
+    assert_eq!(response.status_code(), 200);
+    let mut requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    requests.remove(0)
+}
+
+#[mochi_test]
+fn form_is_url_encoded() {
+    let received = send(|request| request.form(&[("q", "a b+c"), ("lang", "日本"), ("empty", "")]));
+    assert_eq!(received.header("content-type"), Some("application/x-www-form-urlencoded"));
+    assert_eq!(received.body, b"q=a+b%2Bc&lang=%E6%97%A5%E6%9C%AC&empty=");
+}
+
+#[mochi_test]
+fn json_body_is_serialized() {
+    let mut value = ObjectRef::new();
+    value.set("id", "12\"3".into());
+    value.set("page", 2i64.into());
+    value.set("tags", ArrayRef::from(vec!["a", "b"]).into());
+
+    let received = send(|request| request.json_body(&value.into()).unwrap());
+    assert_eq!(received.header("content-type"), Some("application/json"));
+    let body: serde_json::Value = serde_json::from_slice(&received.body).unwrap();
+    assert_eq!(body, serde_json::json!({ "id": "12\"3", "page": 2, "tags": ["a", "b"] }));
+}
+
+#[mochi_test]
+fn multipart_parts_are_delimited_by_the_boundary() {
+    let form = || {
+        Multipart::new()
+            .text("type", "image")
+            .text("na\"me", "line\r\nbreak")
+            .file("file", "cover.jpg", "image/jpeg", [0xff, 0xd8, 0x00, 0xd9])
+    };
+    let received = send(|request| request.multipart(form()));
+
+    let content_type = received.header("content-type").unwrap();
+    let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+    assert!(boundary.len() > "----MochiFormBoundary".len());
+
+    let mut expected = Vec::new();
+    expected.extend(format!("--{boundary}\r\n").bytes());
+    expected.extend(b"Content-Disposition: form-data; name=\"type\"\r\n\r\nimage\r\n");
+    expected.extend(format!("--{boundary}\r\n").bytes());
+    expected.extend(b"Content-Disposition: form-data; name=\"na%22me\"\r\n\r\nline\r\nbreak\r\n");
+    expected.extend(format!("--{boundary}\r\n").bytes());
+    expected.extend(b"Content-Disposition: form-data; name=\"file\"; filename=\"cover.jpg\"\r\n");
+    expected.extend(b"Content-Type: image/jpeg\r\n\r\n");
+    expected.extend([0xff, 0xd8, 0x00, 0xd9]);
+    expected.extend(b"\r\n");
+    expected.extend(format!("--{boundary}--\r\n").bytes());
+    assert_eq!(received.body, expected);
+
+    // The boundary is random, not a counter.
+    let again = send(|request| request.multipart(form()));
+    assert_ne!(again.header("content-type"), Some(content_type));
+}
@@ -0,0 +1,100 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::http::{Cache, CookieJar, Request, RequestMethod};
+use mochi::std::sleep;
+use mochi::test::server::{Route, Server};
+use mochi::test::{host, mochi_test};
+
+fn server() -> Server {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/key").body("secret").header("Set-Cookie", "seen=1"));
+    server
+}
+
+fn get(server: &Server, cache: Cache) -> Request {
+    Request::new(server.url("/key"), RequestMethod::Get).cache(cache)
+}
+
+#[mochi_test]
+fn fresh_responses_are_reused() {
+    let server = server();
+    assert_eq!(get(&server, Cache::new(60)).string().unwrap(), "secret");
+    assert_eq!(get(&server, Cache::new(60)).string().unwrap(), "secret");
+    assert_eq!(server.requests().len(), 1);
+
+    // Without a cache, the request is sent.
+    Request::new(server.url("/key"), RequestMethod::Get).send();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[mochi_test]
+fn error_responses_are_not_cached() {
+    let server = server();
+    server.route(Route::get("/key").status(503).times(1));
+    assert_eq!(get(&server, Cache::new(60)).send().status_code(), 503);
+    assert_eq!(get(&server, Cache::new(60)).send().status_code(), 200);
+    assert_eq!(get(&server, Cache::new(60)).send().status_code(), 200);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[mochi_test]
+fn stale_responses_are_fetched_again() {
+    let server = server();
+    get(&server, Cache::new(1)).send();
+    get(&server, Cache::new(1)).send();
+    assert_eq!(server.requests().len(), 1);
+
+    sleep(1100);
+    get(&server, Cache::new(1)).send();
+    assert_eq!(server.requests().len(), 2);
+    // A longer TTL doesn't bring back a response stored with a shorter one.
+    sleep(1100);
+    get(&server, Cache::new(60)).send();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[mochi_test]
+fn vary_headers_are_part_of_the_key() {
+    let server = server();
+    let cache = || Cache::new(60).vary(&["Referer"]);
+    let from = |referer: &str| get(&server, cache()).header("Referer", referer).send();
+
+    from("https://a.example/");
+    from("https://b.example/");
+    assert_eq!(server.requests().len(), 2);
+    from("https://a.example/");
+    assert_eq!(server.requests().len(), 2);
+
+    // Headers that aren't in `vary` don't matter.
+    get(&server, Cache::new(60)).header("Referer", "https://a.example/").send();
+    get(&server, Cache::new(60)).header("Referer", "https://b.example/").send();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[mochi_test]
+fn persisted_responses_survive_a_reload() {
+    let server = server();
+    get(&server, Cache::new(60).persist()).send();
+    get(&server, Cache::new(60)).send();
+    assert_eq!(host::with(|host| host.cache().len()), 1);
+
+    // Dropping the memory cache is what loading the source again does.
+    Cache::clear();
+    assert_eq!(get(&server, Cache::new(60).persist()).string().unwrap(), "secret");
+    assert_eq!(server.requests().len(), 1);
+
+    Cache::clear();
+    get(&server, Cache::new(60)).send();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[mochi_test]
+fn cached_responses_set_cookies() {
+    let server = server();
+    get(&server, Cache::new(60)).send();
+
+    let jar = CookieJar::new();
+    get(&server, Cache::new(60)).cookie_jar(&jar).send();
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(jar.get("seen").map(|cookie| cookie.value).as_deref(), Some("1"));
+}
@@ -0,0 +1,25 @@
+use mochi::test::fixtures::{Fixtures, Mode};
+use mochi::test::server::{Route, Server};
+
+/// Serves the test's HTTP requests from `tests/fixtures/<name>`.
+///
+/// When recording (`MOCHI_RECORD=1`), requests to `sites` go to a local
+/// server answering with `routes` instead, so the fixtures can be captured
+/// again without the real sites. Keep the server alive for the test.
+pub fn fixtures(name: &str, sites: &[&str], routes: Vec<Route>) -> Option<Server> {
+    let mut fixtures = Fixtures::new(format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name));
+    if fixtures.mode() == Mode::Replay {
+        fixtures.install();
+        return None;
+    }
+
+    let server = Server::start().expect("failed to start the local server");
+    for route in routes {
+        server.route(route);
+    }
+    for site in sites {
+        fixtures = fixtures.redirect(*site, server.url(""));
+    }
+    fixtures.install();
+    Some(server)
+}
@@ -0,0 +1,157 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::http::{Cookie, CookieJar, Request, RequestMethod};
+use mochi::std::now;
+use mochi::test::mochi_test;
+use mochi::test::server::{Route, Server};
+
+#[mochi_test]
+fn host_only_cookies_match_their_host() {
+    let cookie = Cookie::parse("id=1", "https://example.com/a/b").unwrap();
+    assert_eq!(cookie.domain, "example.com");
+    assert!(cookie.host_only);
+    assert_eq!(cookie.path, "/a");
+    assert!(cookie.matches("https://example.com/a"));
+    assert!(cookie.matches("https://EXAMPLE.com/a/c"));
+    assert!(!cookie.matches("https://www.example.com/a"));
+    assert!(!cookie.matches("https://example.org/a"));
+}
+
+#[mochi_test]
+fn domain_cookies_match_subdomains() {
+    let cookie = Cookie::parse("id=1; Domain=.Example.com", "https://www.example.com/").unwrap();
+    assert_eq!(cookie.domain, "example.com");
+    assert!(!cookie.host_only);
+    assert!(cookie.matches("https://example.com/"));
+    assert!(cookie.matches("https://www.example.com/"));
+    assert!(cookie.matches("https://a.b.example.com/"));
+    assert!(!cookie.matches("https://badexample.com/"));
+}
+
+#[mochi_test]
+fn cookies_for_other_domains_are_rejected() {
+    assert_eq!(Cookie::parse("id=1; Domain=example.org", "https://example.com/"), None);
+    assert_eq!(Cookie::parse("id=1; Domain=www.example.com", "https://example.com/"), None);
+    assert_eq!(Cookie::parse("id=1; Domain=ample.com", "https://example.com/"), None);
+    assert_eq!(Cookie::parse("=1", "https://example.com/"), None);
+    assert_eq!(Cookie::parse("id", "https://example.com/"), None);
+}
+
+#[mochi_test]
+fn paths_match_on_segments() {
+    let cookie = Cookie::parse("id=1; Path=/docs", "https://example.com/").unwrap();
+    assert!(cookie.matches("https://example.com/docs"));
+    assert!(cookie.matches("https://example.com/docs/"));
+    assert!(cookie.matches("https://example.com/docs/web"));
+    assert!(!cookie.matches("https://example.com/docsets"));
+    assert!(!cookie.matches("https://example.com/"));
+
+    let cookie = Cookie::parse("id=1; Path=/docs/", "https://example.com/").unwrap();
+    assert!(cookie.matches("https://example.com/docs/web"));
+    assert!(!cookie.matches("https://example.com/docs"));
+}
+
+#[mochi_test]
+fn secure_cookies_are_only_sent_over_https() {
+    let cookie = Cookie::parse("id=1; Secure; HttpOnly", "https://example.com/").unwrap();
+    assert!(cookie.secure);
+    assert!(cookie.http_only);
+    assert!(cookie.matches("https://example.com/"));
+    assert!(!cookie.matches("http://example.com/"));
+}
+
+#[mochi_test]
+fn expires_is_parsed_in_every_date_format() {
+    // 2015-10-21T07:28:00Z
+    let expected = Some(1_445_412_480_000);
+    for date in [
+        "Wed, 21 Oct 2015 07:28:00 GMT",
+        "Wednesday, 21-Oct-15 07:28:00 GMT",
+        "Wed Oct 21 07:28:00 2015",
+    ] {
+        let cookie = Cookie::parse(format!("id=1; Expires={date}"), "https://example.com/").unwrap();
+        assert_eq!(cookie.expires, expected, "{date}");
+        assert!(cookie.is_expired());
+    }
+
+    let cookie = Cookie::parse("id=1; Expires=not a date", "https://example.com/").unwrap();
+    assert_eq!(cookie.expires, None);
+    assert!(!cookie.is_expired());
+}
+
+#[mochi_test]
+fn max_age_takes_precedence_over_expires() {
+    let before = now() as i64;
+    let cookie = Cookie::parse(
+        "id=1; Max-Age=3600; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+        "https://example.com/",
+    )
+    .unwrap();
+    let expires = cookie.expires.unwrap();
+    assert!(expires >= before + 3_600_000 && expires <= now() as i64 + 3_600_000);
+    assert!(!cookie.is_expired());
+
+    let cookie = Cookie::parse("id=1; Max-Age=0; Expires=Fri, 01 Jan 2100 00:00:00 GMT", "https://example.com/").unwrap();
+    assert!(cookie.is_expired());
+}
+
+#[mochi_test]
+fn expired_cookies_are_removed_from_the_jar() {
+    let jar = CookieJar::new();
+    jar.set_cookie("https://example.com/", "a=1");
+    jar.set_cookie("https://example.com/", "b=2; Max-Age=3600");
+    jar.set_cookie("https://example.com/", "c=3; Expires=Fri, 01 Jan 2100 00:00:00 GMT");
+    assert_eq!(jar.header("https://example.com/").as_deref(), Some("a=1; b=2; c=3"));
+
+    jar.set_cookie("https://example.com/", "a=; Max-Age=0");
+    jar.set_cookie("https://example.com/", "c=; Expires=Thu, 01 Jan 1970 00:00:00 GMT");
+    assert_eq!(jar.header("https://example.com/").as_deref(), Some("b=2"));
+
+    // A cookie that has expired since it was stored isn't sent either.
+    jar.insert(Cookie {
+        expires: Some(now() as i64 - 1),
+        ..Cookie::parse("d=4", "https://example.com/").unwrap()
+    });
+    assert_eq!(jar.header("https://example.com/").as_deref(), Some("b=2"));
+    assert_eq!(jar.get("d"), None);
+}
+
+#[mochi_test]
+fn deleting_a_cookie_needs_the_same_domain_and_path() {
+    let jar = CookieJar::new();
+    jar.set_cookie("https://www.example.com/", "id=1; Domain=example.com");
+    jar.set_cookie("https://www.example.com/", "id=; Max-Age=0");
+    assert_eq!(jar.header("https://www.example.com/").as_deref(), Some("id=1"));
+
+    jar.set_cookie("https://www.example.com/", "id=; Domain=example.com; Max-Age=0");
+    assert_eq!(jar.header("https://www.example.com/"), None);
+}
+
+#[mochi_test]
+fn header_puts_longer_paths_first() {
+    let jar = CookieJar::new();
+    jar.set_cookie("https://example.com/", "root=1; Path=/");
+    jar.set_cookie("https://example.com/", "docs=2; Path=/docs");
+    jar.set_cookie("https://example.com/", "other=3; Path=/other");
+    assert_eq!(jar.header("https://example.com/docs/a").as_deref(), Some("docs=2; root=1"));
+    assert_eq!(jar.header("https://example.org/"), None);
+}
+
+#[mochi_test]
+fn requests_send_the_cookies_for_the_url_they_are_sent_to() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/home"));
+    let jar = CookieJar::new();
+    jar.set_cookie("https://example.com/", "site=1");
+    jar.set_cookie(&server.url("/"), "local=2");
+
+    let request = Request::new("https://example.com/home", RequestMethod::Get)
+        .header("Cookie", "own=0")
+        .cookie_jar(&jar)
+        .set_url(server.url("/home"));
+    assert_eq!(request.send().status_code(), 200);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("cookie"), Some("own=0; local=2"));
+}
@@ -0,0 +1,87 @@
+extern crate mochi_rs as mochi;
+
+use mochi::test::{from_host, mochi_test, to_host, Value};
+use mochi::{FromHost, IntoHost};
+
+#[derive(IntoHost, FromHost, Debug, Clone, PartialEq)]
+struct Episode {
+    episode_number: i64,
+    title: String,
+    #[mochi(rename = "thumbnail")]
+    poster_image: Option<String>,
+}
+
+#[derive(IntoHost, FromHost, Debug, Clone, PartialEq)]
+struct Season {
+    id: String,
+    is_finished: bool,
+    rating: f64,
+    episodes: Vec<Episode>,
+    next_episode: Option<Episode>,
+}
+
+#[derive(IntoHost, FromHost, Debug, Clone, PartialEq)]
+struct Labelled<T> {
+    label: String,
+    values: Vec<T>,
+}
+
+fn season() -> Season {
+    Season {
+        id: "s1".into(),
+        is_finished: false,
+        rating: 8.5,
+        episodes: vec![
+            Episode { episode_number: 1, title: "One".into(), poster_image: Some("1.jpg".into()) },
+            Episode { episode_number: 2, title: "Two".into(), poster_image: None },
+        ],
+        next_episode: None,
+    }
+}
+
+#[mochi_test]
+fn into_host_uses_camel_case_and_renamed_keys() {
+    let value = to_host(season());
+    assert_eq!(value.get("id").and_then(Value::as_str), Some("s1"));
+    assert_eq!(value.get("isFinished").and_then(Value::as_bool), Some(false));
+    assert_eq!(value.get("rating").and_then(Value::as_f64), Some(8.5));
+    assert!(value.get("nextEpisode").is_none());
+
+    let episodes = value.get("episodes").and_then(Value::as_array).unwrap();
+    assert_eq!(episodes.len(), 2);
+    assert_eq!(episodes[0].get("episodeNumber").and_then(Value::as_i64), Some(1));
+    assert_eq!(episodes[0].get("thumbnail").and_then(Value::as_str), Some("1.jpg"));
+    assert!(episodes[0].get("posterImage").is_none());
+    assert!(episodes[1].get("thumbnail").is_none());
+}
+
+#[mochi_test]
+fn nested_structs_round_trip() {
+    let mut season = season();
+    let back: Season = from_host(to_host(season.clone()));
+    assert_eq!(back, season);
+
+    season.next_episode = Some(Episode { episode_number: 3, title: "Three".into(), poster_image: None });
+    let back: Season = from_host(to_host(season.clone()));
+    assert_eq!(back, season);
+}
+
+#[mochi_test]
+fn missing_keys_read_as_defaults() {
+    let episode: Episode = from_host(serde_json::json!({ "title": "Only" }).into());
+    assert_eq!(
+        episode,
+        Episode { episode_number: 0, title: "Only".into(), poster_image: None }
+    );
+}
+
+#[mochi_test]
+fn generic_structs_round_trip() {
+    let labelled = Labelled { label: "seasons".into(), values: vec![season(), season()] };
+    let back: Labelled<Season> = from_host(to_host(labelled.clone()));
+    assert_eq!(back, labelled);
+
+    let labelled = Labelled { label: "numbers".into(), values: vec![1i64, 2, 3] };
+    let back: Labelled<i64> = from_host(to_host(labelled.clone()));
+    assert_eq!(back, labelled);
+}
@@ -0,0 +1,91 @@
+extern crate mochi_rs as mochi;
+
+mod common;
+
+use mochi::error::MochiError;
+use mochi::extractors::{RapidCloud, VidCloud};
+use mochi::test::server::Route;
+use mochi::test::{mochi_test, to_host, Value};
+
+const RAPIDCLOUD_SOURCES: &str = r#"{
+    "sources": "U2FsdGVkX18BAgMEBQYHCFUhz9NHLMdkR3iYvAfPCZ9egAyVOZvYaollz5e+qFMRagbZ+g8X9GUM0Ae7EEE1pGw+E+nvdILDFBB36BAIG+VN3LZE9Zas98UiwbNJVymH",
+    "encrypted": true,
+    "tracks": [
+        { "file": "https://cc.rapid.example/eng.vtt", "label": "English", "kind": "captions", "default": true },
+        { "file": "https://cc.rapid.example/thumbnails.vtt", "kind": "thumbnails" }
+    ],
+    "intro": { "start": 31, "end": 120 },
+    "outro": { "start": 1300, "end": 1390 }
+}"#;
+
+// The key is spread over the sources at the ranges listed in `key.txt`.
+const VIDCLOUD_SOURCES: &str = r#"{
+    "sources": "U2Fsdk3y5GVkX18REhMUecreFRYXGDGqqOTHnNc84mr51WlfcKMEneMjVyAnwqTmfnghlFfuuMCJHg0UibcsAw/MO04M/YuMu2Zpxb+sxfeK3aT7qlPNvtIYWkLyntI4XXwNtiUI",
+    "encrypted": true,
+    "tracks": [
+        { "file": "https://cc.vid.example/spa.vtt", "label": "Spanish", "kind": "captions" }
+    ]
+}"#;
+
+#[mochi_test]
+fn rapidcloud_decrypts_sources() {
+    let _server = common::fixtures(
+        "rapidcloud",
+        &["https://rapid-cloud.co", "https://raw.githubusercontent.com"],
+        vec![
+            Route::get("/ajax/embed-6/getSources?id=fEjafeaEF").body(RAPIDCLOUD_SOURCES),
+            Route::get("/enimax-anime/key/e6/key.txt").body("3b8f1c2a9d7e4f60"),
+        ],
+    );
+
+    let response = to_host(RapidCloud::extract("https://rapid-cloud.co/embed-6/fEjafeaEF?k=1"));
+    let links = response.get("links").and_then(Value::as_array).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(
+        links[0].get("url").and_then(Value::as_str),
+        Some("https://cdn.rapid.example/hls/fEjafeaEF/master.m3u8")
+    );
+    let subtitles = response.get("subtitles").and_then(Value::as_array).unwrap();
+    assert_eq!(subtitles.len(), 1);
+    assert_eq!(subtitles[0].get("name").and_then(Value::as_str), Some("English"));
+    assert_eq!(response.get("skipTimes").and_then(Value::as_array).map(Vec::len), Some(2));
+}
+
+#[mochi_test]
+fn rapidcloud_reports_a_missing_video() {
+    let _server = common::fixtures(
+        "rapidcloud-missing",
+        &["https://rapid-cloud.co"],
+        vec![Route::get("/ajax/embed-6/getSources?id=gone").status(404)],
+    );
+
+    let Err(error) = RapidCloud::extract("https://rapid-cloud.co/embed-6/gone") else {
+        panic!("a 404 should fail");
+    };
+    assert_eq!(
+        error.to_string(),
+        "while fetching rapidcloud sources: request to https://rapid-cloud.co/ajax/embed-6/getSources?id=gone failed with status 404"
+    );
+    assert!(matches!(error.root_cause(), MochiError::Http { status: 404, .. }));
+}
+
+#[mochi_test]
+fn vidcloud_decrypts_sources() {
+    let _server = common::fixtures(
+        "vidcloud",
+        &["https://dokicloud.one", "https://raw.githubusercontent.com"],
+        vec![
+            Route::get("/ajax/embed-4/getSources?id=xD2Jk").body(VIDCLOUD_SOURCES),
+            Route::get("/enimax-anime/key/e4/key.txt").body("[[5,9],[20,24]]"),
+        ],
+    );
+
+    let video_url = "https://dokicloud.one/embed-4/xD2Jk?z=";
+    let response = to_host(VidCloud::extract(video_url, false));
+    let links = response.get("links").and_then(Value::as_array).unwrap();
+    assert_eq!(links[0].get("url").and_then(Value::as_str), Some("https://cdn.vid.example/hls/xD2Jk/master.m3u8"));
+    let subtitles = response.get("subtitles").and_then(Value::as_array).unwrap();
+    assert_eq!(subtitles[0].get("url").and_then(Value::as_str), Some("https://cc.vid.example/spa.vtt"));
+    let referer = response.get("headers").and_then(|headers| headers.get("Referer"));
+    assert_eq!(referer.and_then(Value::as_str), Some(video_url));
+}
@@ -0,0 +1,162 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::html::Node;
+use mochi::test::mochi_test;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<body>
+    <ul class="episodes">
+        <li id="ep-1" class="episode">One</li>
+        <li id="ep-2" class="episode active">Two</li>
+        <li id="ep-3" class="episode">Three</li>
+    </ul>
+</body>
+</html>
+"#;
+
+fn document() -> Node {
+    Node::new(PAGE).unwrap()
+}
+
+fn ids(list: impl Iterator<Item = Node>) -> Vec<String> {
+    list.map(|node| node.attr("id")).collect()
+}
+
+#[mochi_test]
+fn node_lists_iterate_from_both_ends() {
+    let document = document();
+    assert_eq!(ids(document.select("li")), ["ep-1", "ep-2", "ep-3"]);
+    assert_eq!(ids(document.select("li").rev()), ["ep-3", "ep-2", "ep-1"]);
+
+    let mut list = document.select("li");
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.next().map(|node| node.attr("id")).as_deref(), Some("ep-1"));
+    assert_eq!(list.next_back().map(|node| node.attr("id")).as_deref(), Some("ep-3"));
+    assert_eq!(list.len(), 1);
+    assert_eq!(ids(list), ["ep-2"]);
+
+    let mut list = document.select("li");
+    assert_eq!(list.nth(1).map(|node| node.attr("id")).as_deref(), Some("ep-2"));
+    assert_eq!(list.nth(5).map(|node| node.attr("id")), None);
+    assert!(list.is_empty());
+}
+
+#[mochi_test]
+fn first_and_get_keep_the_list() {
+    let list = document().select("li");
+    assert_eq!(list.first().map(|node| node.attr("id")).as_deref(), Some("ep-1"));
+    assert_eq!(list.get(1).map(|node| node.attr("id")).as_deref(), Some("ep-2"));
+    assert_eq!(list.get(3).map(|node| node.attr("id")), None);
+    assert_eq!(list.len(), 3);
+}
+
+#[mochi_test]
+fn last_is_the_last_element_left() {
+    let list = document().select("li");
+    assert_eq!(list.clone().last().map(|node| node.attr("id")).as_deref(), Some("ep-3"));
+    assert_eq!(list.len(), 3);
+
+    let mut list = list;
+    list.next_back();
+    assert_eq!(list.last().map(|node| node.attr("id")).as_deref(), Some("ep-2"));
+}
+
+#[mochi_test]
+fn empty_lists_have_no_first_or_last() {
+    let list = document().select(".missing");
+    assert!(list.is_empty());
+    assert!(list.first().is_none());
+    assert!(list.clone().last().is_none());
+    assert!(list.get(0).is_none());
+    assert_eq!(list.text(), "");
+    assert_eq!(list.count(), 0);
+}
+
+#[mochi_test]
+fn lists_act_on_every_match() {
+    let list = document().select("li");
+    assert_eq!(list.text_all(), ["One", "Two", "Three"]);
+    assert_eq!(list.attr_all("id"), ["ep-1", "ep-2", "ep-3"]);
+    assert_eq!(list.attr("id"), "ep-1");
+    assert_eq!(list.text(), "One Two Three");
+    assert_eq!(list.select(".active").text(), "Two");
+    assert_eq!(list.select("ul").text(), "");
+}
+
+#[mochi_test]
+fn siblings_are_next_and_previous() {
+    let document = document();
+    let active = document.select(".active").first().unwrap();
+    assert_eq!(active.next_sibling().map(|node| node.attr("id")).as_deref(), Some("ep-3"));
+    assert_eq!(active.previous_sibling().map(|node| node.attr("id")).as_deref(), Some("ep-1"));
+    let last = active.next_sibling().unwrap();
+    assert!(last.next_sibling().is_none());
+}
+
+#[mochi_test]
+fn parent_and_closest_walk_up() {
+    let document = document();
+    let active = document.select_first(".active").unwrap();
+    assert_eq!(active.parent().unwrap().class_name(), "episodes");
+    assert_eq!(active.closest("ul").unwrap().class_name(), "episodes");
+    assert_eq!(active.closest("li").unwrap().id(), "ep-2");
+    assert_eq!(active.closest("body").unwrap().tag_name(), "body");
+    assert!(active.closest("ol").is_none());
+
+    let html = document.select_first("html").unwrap();
+    assert!(html.parent().is_none());
+    assert!(document.parent().is_none());
+}
+
+#[mochi_test]
+fn children_and_siblings_are_elements_only() {
+    let document = document();
+    assert_eq!(document.children().map(|node| node.tag_name()).collect::<Vec<_>>(), ["html"]);
+
+    let list = document.select_first("ul").unwrap();
+    assert_eq!(ids(list.children()), ["ep-1", "ep-2", "ep-3"]);
+
+    let active = document.select_first(".active").unwrap();
+    assert_eq!(ids(active.siblings()), ["ep-1", "ep-3"]);
+    assert!(active.children().is_empty());
+}
+
+#[mochi_test]
+fn index_in_parent_counts_elements() {
+    let document = document();
+    let indexes = document.select("li").map(|node| node.index_in_parent()).collect::<Vec<_>>();
+    assert_eq!(indexes, [Some(0), Some(1), Some(2)]);
+    assert_eq!(document.index_in_parent(), None);
+}
+
+#[mochi_test]
+fn attributes_lists_every_pair() {
+    let active = document().select_first(".active").unwrap();
+    let mut attributes = active.attributes();
+    attributes.sort();
+    assert_eq!(
+        attributes,
+        [
+            ("class".to_string(), "episode active".to_string()),
+            ("id".to_string(), "ep-2".to_string()),
+        ]
+    );
+    assert!(document().attributes().is_empty());
+}
+
+#[mochi_test]
+fn select_first_misses_are_none() {
+    let document = document();
+    assert_eq!(document.select_first("li").unwrap().id(), "ep-1");
+    assert!(document.select_first(".missing").is_none());
+    assert!(document.select_first("li[").is_none());
+}
+
+#[mochi_test]
+fn elements_outlive_their_document() {
+    let active = document().select_first(".active").unwrap();
+    assert_eq!(active.parent().unwrap().class_name(), "episodes");
+    assert_eq!(active.next_sibling().unwrap().id(), "ep-3");
+    assert_eq!(active.index_in_parent(), Some(1));
+}
@@ -0,0 +1,68 @@
+extern crate mochi_rs as mochi;
+
+use mochi::error::MochiError;
+use mochi::std::{ArrayRef, ObjectRef, PtrRef};
+use mochi::std::json;
+use mochi::test::mochi_test;
+
+const NESTED: &str = r#"{"episodes":[{"id":1,"title":"Pilot"},{"id":2,"title":null}],"meta":{"count":2,"done":false,"rating":4.5},"tags":["a","b"]}"#;
+
+#[mochi_test]
+fn nested_values_round_trip() {
+    let value = json::parse(NESTED).unwrap();
+    let string = json::stringify(&value).unwrap();
+    assert_eq!(string, NESTED);
+    assert_eq!(json::parse(&string).unwrap().to_json_string().unwrap(), NESTED);
+
+    let object = value.as_object().unwrap();
+    assert_eq!(object.to_json_string().unwrap(), NESTED);
+    let episodes = object.get("episodes").as_array().unwrap();
+    assert_eq!(
+        episodes.to_json_string().unwrap(),
+        r#"[{"id":1,"title":"Pilot"},{"id":2,"title":null}]"#
+    );
+}
+
+#[mochi_test]
+fn values_built_in_rust_are_stringified() {
+    let mut tags = ArrayRef::new();
+    tags.insert(PtrRef::from("a"));
+    tags.insert(PtrRef::from(2i64));
+    tags.insert(PtrRef::null_value());
+
+    let mut object = ObjectRef::new();
+    object.set("name", PtrRef::from("mochi"));
+    object.set("ok", PtrRef::from(true));
+    object.set("tags", tags.into());
+    assert_eq!(object.to_json_string().unwrap(), r#"{"name":"mochi","ok":true,"tags":["a",2,null]}"#);
+}
+
+#[mochi_test]
+fn pretty_output_is_indented() {
+    let value = json::parse(r#"{"a":[1,{"b":null}],"c":{}}"#).unwrap();
+    let expected = "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    }\n  ],\n  \"c\": {}\n}";
+    assert_eq!(json::stringify_pretty(&value).unwrap(), expected);
+    assert_eq!(value.to_json_string_pretty().unwrap(), expected);
+    assert_eq!(value.as_object().unwrap().to_json_string_pretty().unwrap(), expected);
+}
+
+#[mochi_test]
+fn strings_are_escaped() {
+    let value = PtrRef::from("quote \" backslash \\ newline \n tab \t bell \u{7} é");
+    let string = json::stringify(&value).unwrap();
+    assert_eq!(string, r#""quote \" backslash \\ newline \n tab \t bell \u0007 é""#);
+    assert_eq!(
+        json::parse(&string).unwrap().as_string().unwrap(),
+        "quote \" backslash \\ newline \n tab \t bell \u{7} é"
+    );
+}
+
+#[mochi_test]
+fn unknown_pointers_cannot_be_stringified() {
+    assert!(matches!(json::stringify(&PtrRef::null()), Err(MochiError::JsonStringifyError)));
+    assert!(matches!(json::stringify_pretty(&PtrRef::null()), Err(MochiError::JsonStringifyError)));
+    assert!(matches!(PtrRef::new(123_456).to_json_string(), Err(MochiError::JsonStringifyError)));
+
+    // A null held by the host is a value like any other.
+    assert_eq!(json::stringify(&PtrRef::null_value()).unwrap(), "null");
+}
@@ -0,0 +1,16 @@
+extern crate mochi_rs as mochi;
+
+use mochi::test::{host, mochi_test};
+
+#[mochi_test]
+fn log_records_reach_the_console() {
+    mochi::std::log::init(log::LevelFilter::Info);
+    log::info!(target: "source", "found {} items", 20);
+    log::warn!(target: "source", "slow response");
+    log::error!(target: "source", "status {}", 503);
+    log::debug!(target: "source", "left out");
+    assert_eq!(
+        host::with(|host| host.logs().to_vec()),
+        ["[info] [source] found 20 items", "[warn] [source] slow response", "[error] [source] status 503"]
+    );
+}
@@ -0,0 +1,43 @@
+extern crate mochi_rs as mochi;
+
+use mochi::test::{host, mochi_test};
+
+// The macros have to work next to a local `core`.
+#[allow(dead_code)]
+mod core {}
+
+fn logs() -> Vec<String> {
+    host::with(|host| host.logs().to_vec())
+}
+
+#[mochi_test]
+fn print_macros_write_lines() {
+    let name = "mochi";
+    mochi::print!("hello {}", name);
+    mochi::println!("{name} has {} sources", 2);
+    mochi::println!();
+    mochi::eprintln!("failed: {:?}", Some(1));
+    mochi::eprintln!();
+    assert_eq!(
+        logs(),
+        ["hello mochi", "mochi has 2 sources", "", "[error] failed: Some(1)", "[error] "]
+    );
+}
+
+#[mochi_test]
+fn level_macros_prefix_their_level() {
+    mochi::debug!("page {}", 1);
+    mochi::info!("found {} items", 20);
+    mochi::warn!("slow response");
+    mochi::error!("status {status}", status = 503);
+    assert_eq!(
+        logs(),
+        ["[debug] page 1", "[info] found 20 items", "[warn] slow response", "[error] status 503"]
+    );
+}
+
+#[mochi_test]
+fn format_builds_a_string() {
+    let url: String = mochi::format!("https://{}/{}", "fake.to", 1);
+    assert_eq!(url, "https://fake.to/1");
+}
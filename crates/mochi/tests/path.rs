@@ -0,0 +1,103 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::json;
+use mochi::std::PtrRef;
+use mochi::test::mochi_test;
+
+const DATA: &str = r#"{
+    "data": {
+        "items": [
+            { "id": 1, "file": "a.m3u8", "hd": true },
+            { "id": 2 },
+            { "id": 3, "file": "c.m3u8", "score": 7.5 }
+        ],
+        "a/b": "slash",
+        "m~n": "tilde",
+        "x.y": "dot",
+        "": "empty"
+    }
+}"#;
+
+fn data() -> PtrRef {
+    json::parse(DATA).unwrap()
+}
+
+fn strings(values: mochi::std::ArrayRef) -> Vec<String> {
+    values.map(|value| value.as_string().unwrap()).collect()
+}
+
+#[mochi_test]
+fn json_pointer_looks_up_keys_and_indexes() {
+    let data = data();
+    assert_eq!(data.json_pointer_str("/data/items/0/file").unwrap(), "a.m3u8");
+    assert_eq!(data.json_pointer_i64("/data/items/2/id").unwrap(), 3);
+    assert_eq!(data.json_pointer_f64("/data/items/2/score").unwrap(), 7.5);
+    assert!(data.json_pointer_bool("/data/items/0/hd").unwrap());
+    assert_eq!(data.json_pointer("").json_pointer_i64("/data/items/1/id").unwrap(), 2);
+    assert_eq!(data.json_pointer_str("/data/").unwrap(), "empty");
+}
+
+#[mochi_test]
+fn json_pointer_unescapes_tokens() {
+    let data = data();
+    assert_eq!(data.json_pointer_str("/data/a~1b").unwrap(), "slash");
+    assert_eq!(data.json_pointer_str("/data/m~0n").unwrap(), "tilde");
+    // `~01` is `~1` once unescaped, not `/`.
+    assert!(data.json_pointer("/data/m~01n").is_none());
+}
+
+#[mochi_test]
+fn json_pointer_misses_are_null() {
+    let data = data();
+    assert!(data.json_pointer("/data/items/3").is_none());
+    assert!(data.json_pointer("/data/items/-1").is_none());
+    assert!(data.json_pointer("/data/items/01").is_none());
+    assert!(data.json_pointer("/data/items/+1").is_none());
+    assert!(data.json_pointer("/data/items/first").is_none());
+    assert!(data.json_pointer("/data/items/1/file").is_none());
+    assert!(data.json_pointer("/data/items/0/file/x").is_none());
+    assert!(data.json_pointer("data/items").is_none());
+    assert!(data.json_pointer_str("/missing").is_err());
+    assert!(data.json_pointer_i64("/data/items").is_err());
+}
+
+#[mochi_test]
+fn path_looks_up_keys_and_indexes() {
+    let data = data();
+    assert_eq!(data.path("data.items[0].file").as_string().unwrap(), "a.m3u8");
+    assert_eq!(data.path("data.items[-1].id").as_int().unwrap(), 3);
+    assert_eq!(data.path(r#"data["x.y"]"#).as_string().unwrap(), "dot");
+    assert_eq!(data.path("data['a/b']").as_string().unwrap(), "slash");
+    assert_eq!(data.path("").path("data.items[1].id").as_int().unwrap(), 2);
+}
+
+#[mochi_test]
+fn path_misses_are_null() {
+    let data = data();
+    assert!(data.path("data.items[3]").is_none());
+    assert!(data.path("data.items[-4]").is_none());
+    assert!(data.path("data.items[1].file").is_none());
+    assert!(data.path("data.x.y").is_none());
+    // Malformed paths.
+    assert!(data.path("data..items").is_none());
+    assert!(data.path(".data").is_none());
+    assert!(data.path("data.").is_none());
+    assert!(data.path("data.items[0").is_none());
+    assert!(data.path("data.items[first]").is_none());
+}
+
+#[mochi_test]
+fn wildcards_match_every_value() {
+    let data = data();
+    assert_eq!(strings(data.path_all("data.items[*].file")), ["a.m3u8", "c.m3u8"]);
+    assert_eq!(strings(data.path_all("data.items.*.file")), ["a.m3u8", "c.m3u8"]);
+
+    let ids = data.path("data.items[*].id").as_array().unwrap();
+    assert_eq!(ids.map(|id| id.as_int().unwrap()).collect::<Vec<_>>(), [1, 2, 3]);
+
+    assert_eq!(data.path_all("data.items[*]").len(), 3);
+    assert_eq!(data.path_all("data.items[*].missing").len(), 0);
+    assert_eq!(data.path_all("data.items[0].file").len(), 1);
+    assert_eq!(data.path_all("data.items[9]").len(), 0);
+    assert_eq!(data.path_all("data..items").len(), 0);
+}
@@ -0,0 +1,112 @@
+extern crate mochi_rs as mochi;
+
+mod common;
+
+use mochi::error::Result;
+use mochi::std::http::{CookieJar, Request, RequestMethod};
+use mochi::std::url::encode;
+use mochi::structs::meta::*;
+use mochi::test::server::Route;
+use mochi::test::{from_host, mochi_test, to_host, Value};
+use serde_json::json;
+
+const SEARCH_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<body>
+    <div class="film_list-wrap">
+        <div class="flw-item">
+            <img class="film-poster-img" data-src="https://img.fake.to/naruto.jpg">
+            <h3 class="film-name"><a href="/naruto-677" title="Naruto">Naruto</a></h3>
+        </div>
+        <div class="flw-item">
+            <img class="film-poster-img" data-src="https://img.fake.to/shippuden.jpg">
+            <h3 class="film-name"><a href="/naruto-shippuden-355" title="Naruto: Shippuden">Naruto: Shippuden</a></h3>
+        </div>
+    </div>
+    <ul class="pagination"><li class="page-item active"><a>1</a></li><li class="page-item"><a href="/search?keyword=naruto&amp;page=2">2</a></li></ul>
+</body>
+</html>
+"#;
+
+struct Fake;
+
+impl Meta for Fake {
+    fn search_filters() -> SearchFilters {
+        SearchFilters { filters: vec![] }
+    }
+
+    fn search(search_query: SearchQuery) -> Result<Paging<Playlist>> {
+        let page = search_query.page.unwrap_or_else(|| "1".into());
+        let url = format!("https://fake.to/search?keyword={}&page={}", encode(&search_query.query), page);
+        let document = Request::new(url, RequestMethod::Get)
+            .send()
+            .error_for_status()?
+            .html()?;
+        let items = document
+            .select(".flw-item")
+            .map(|item| {
+                let link = item.select(".film-name a");
+                Playlist {
+                    id: link.attr("href"),
+                    title: Some(link.attr("title")),
+                    poster_image: Some(item.select(".film-poster-img").attr("data-src")),
+                    banner_image: None,
+                    url: link.attr("abs:href"),
+                    status: PlaylistStatus::Unknown,
+                    playlist_type: PlaylistType::Video,
+                }
+            })
+            .collect();
+        let next_page = document.select(".page-item.active").first().and_then(|active| active.next_sibling());
+        Ok(Paging {
+            id: page,
+            previous_page: None,
+            next_page: next_page.map(|next| next.text()),
+            items,
+        })
+    }
+
+    fn discover_listings() -> Result<DiscoverListings> {
+        Ok(DiscoverListings(vec![]))
+    }
+
+    fn playlist_details(_id: String) -> Result<PlaylistDetails> {
+        Err(mochi::error::MochiError::Unimplemented)
+    }
+}
+
+#[mochi_test]
+fn search_replays_the_recorded_page() {
+    let _server = common::fixtures(
+        "search",
+        &["https://fake.to"],
+        vec![Route::get("/search?keyword=naruto&page=1").header("Content-Type", "text/html").body(SEARCH_PAGE)],
+    );
+
+    let query: SearchQuery = from_host(Value::from(json!({ "query": "naruto", "filters": [] })));
+    let paging = to_host(Fake::search(query));
+    assert_eq!(paging.get("nextPage").and_then(Value::as_str), Some("2"));
+    let items = paging.get("items").and_then(Value::as_array).unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].get("id").and_then(Value::as_str), Some("/naruto-677"));
+    assert_eq!(items[1].get("title").and_then(Value::as_str), Some("Naruto: Shippuden"));
+    assert_eq!(items[1].get("posterImage").and_then(Value::as_str), Some("https://img.fake.to/shippuden.jpg"));
+}
+
+#[mochi_test]
+fn cookies_are_not_part_of_the_fixture() {
+    let _server = common::fixtures(
+        "cookies",
+        &["https://fake.to"],
+        vec![Route::get("/home").body("welcome back")],
+    );
+
+    // A session cookie differs on every run, the fixture has to match anyway.
+    let jar = CookieJar::new();
+    jar.set_cookie("https://fake.to/", format!("session={}", mochi::std::now()));
+    let body = Request::new("https://fake.to/home", RequestMethod::Get)
+        .cookie_jar(&jar)
+        .string()
+        .unwrap();
+    assert_eq!(body, "welcome back");
+}
@@ -0,0 +1,99 @@
+extern crate mochi_rs as mochi;
+
+use mochi::error::MochiError;
+use mochi::std::http::{Request, RequestMethod};
+use mochi::test::mochi_test;
+use mochi::test::server::{Route, Server};
+
+#[mochi_test]
+fn url_is_the_one_after_redirects() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/old").status(302).header("Location", "/new"));
+    server.route(Route::get("/new").body("moved"));
+
+    let response = Request::new(server.url("/old"), RequestMethod::Get).send();
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.url(), server.url("/new"));
+    assert_eq!(response.string().unwrap(), "moved");
+
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, ["/old", "/new"]);
+}
+
+#[mochi_test]
+fn repeated_headers_are_all_kept() {
+    let server = Server::start().unwrap();
+    server.route(
+        Route::get("/login")
+            .header("Set-Cookie", "session=abc; Path=/")
+            .header("Content-Type", "text/html; charset=utf-8")
+            .header("Set-Cookie", "theme=dark"),
+    );
+
+    let response = Request::new(server.url("/login"), RequestMethod::Get).send();
+    assert_eq!(
+        response.header_all("set-cookie").collect::<Vec<_>>(),
+        ["session=abc; Path=/", "theme=dark"]
+    );
+    assert_eq!(response.header("SET-COOKIE"), Some("session=abc; Path=/"));
+    assert_eq!(response.header("Content-Type"), Some("text/html; charset=utf-8"));
+    assert_eq!(response.content_type(), Some("text/html"));
+    assert_eq!(response.header("X-Missing"), None);
+    assert_eq!(response.header_all("X-Missing").count(), 0);
+
+    let cookies: Vec<&str> = response
+        .headers()
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("set-cookie"))
+        .map(|(_, value)| value.as_str())
+        .collect();
+    assert_eq!(cookies, ["session=abc; Path=/", "theme=dark"]);
+}
+
+#[mochi_test]
+fn content_type_is_none_without_the_header() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/plain").body("text"));
+
+    let response = Request::new(server.url("/plain"), RequestMethod::Get).send();
+    assert_eq!(response.content_type(), None);
+}
+
+#[mochi_test]
+fn error_statuses_become_http_errors() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/ok").body("ok"));
+    server.route(Route::get("/redirect").status(302).header("Location", "/missing"));
+    server.route(Route::get("/broken").status(500));
+
+    let ok = Request::new(server.url("/ok"), RequestMethod::Get).send();
+    assert!(ok.is_success());
+    assert_eq!(ok.error_for_status().unwrap().string().unwrap(), "ok");
+
+    // The error has the URL the status came from.
+    let missing = Request::new(server.url("/redirect"), RequestMethod::Get).send();
+    assert_eq!(missing.status_code(), 404);
+    assert!(!missing.is_success());
+    assert_eq!(
+        missing.error_for_status().unwrap_err(),
+        MochiError::Http { status: 404, url: server.url("/missing") }
+    );
+
+    let broken = Request::new(server.url("/broken"), RequestMethod::Get).send();
+    assert_eq!(
+        broken.error_for_status().unwrap_err(),
+        MochiError::Http { status: 500, url: server.url("/broken") }
+    );
+}
+
+#[mochi_test]
+fn unreachable_hosts_fail_with_no_status() {
+    let unreachable = Server::start().unwrap().url("/gone");
+
+    let response = Request::new(&unreachable, RequestMethod::Get).send();
+    assert_eq!(response.status_code(), -1);
+    assert!(!response.is_success());
+    assert!(response.headers().is_empty());
+    assert!(response.data().is_empty());
+    assert_eq!(response.error_for_status().unwrap_err(), MochiError::Http { status: -1, url: unreachable });
+}
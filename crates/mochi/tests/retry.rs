@@ -0,0 +1,129 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::http::{CookieJar, Request, RequestMethod, RetryPolicy};
+use mochi::std::now;
+use mochi::test::mochi_test;
+use mochi::test::server::{Route, Server};
+
+/// A server answering `/flaky` with `status` the first `failures` times, and
+/// `200` after that.
+fn flaky(status: u16, failures: usize) -> Server {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/flaky").body("ok"));
+    server.route(Route::get("/flaky").status(status).times(failures));
+    server
+}
+
+fn get(server: &Server) -> Request {
+    Request::new(server.url("/flaky"), RequestMethod::Get)
+}
+
+#[mochi_test]
+fn retries_with_exponential_backoff() {
+    let server = flaky(503, 2);
+    let start = now();
+    let response = get(&server).retry(RetryPolicy::new().backoff(40)).send();
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.string().unwrap(), "ok");
+    assert_eq!(server.requests().len(), 3);
+    // 40ms, then 80ms.
+    assert!(now() - start >= 120.0);
+}
+
+#[mochi_test]
+fn backoff_is_capped_by_max_backoff() {
+    let server = flaky(503, 3);
+    let start = now();
+    let policy = RetryPolicy::new().backoff(200).max_backoff(30);
+    assert_eq!(get(&server).retry(policy).send().status_code(), 200);
+    assert_eq!(server.requests().len(), 4);
+    // Uncapped, the waits would be 200ms, 400ms and 800ms.
+    let elapsed = now() - start;
+    assert!((90.0..600.0).contains(&elapsed), "{elapsed}");
+}
+
+#[mochi_test]
+fn gives_up_after_max_retries() {
+    let server = flaky(503, 10);
+    let response = get(&server).retry(RetryPolicy::new().backoff(1).max_retries(2)).send();
+    assert_eq!(response.status_code(), 503);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[mochi_test]
+fn only_statuses_in_retry_on_are_retried() {
+    let server = flaky(500, 1);
+    let policy = RetryPolicy::new().backoff(1).retry_on(&[429]);
+    assert_eq!(get(&server).retry(policy).send().status_code(), 500);
+    assert_eq!(server.requests().len(), 1);
+
+    let server = flaky(429, 1);
+    let policy = RetryPolicy::new().backoff(1).retry_on(&[429]);
+    assert_eq!(get(&server).retry(policy).send().status_code(), 200);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[mochi_test]
+fn retry_after_is_waited_for_up_to_max_backoff() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/flaky").body("ok"));
+    server.route(Route::get("/flaky").status(429).header("Retry-After", "1").times(1));
+    let start = now();
+    assert_eq!(get(&server).retry(RetryPolicy::new().backoff(1)).send().status_code(), 200);
+    assert!(now() - start >= 1000.0);
+
+    server.route(Route::get("/flaky").status(429).header("Retry-After", "60").times(1));
+    let start = now();
+    let policy = RetryPolicy::new().backoff(1).max_backoff(50);
+    assert_eq!(get(&server).retry(policy).send().status_code(), 200);
+    let elapsed = now() - start;
+    assert!((50.0..1000.0).contains(&elapsed), "{elapsed}");
+}
+
+#[mochi_test]
+fn requests_to_a_host_are_rate_limited() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/flaky"));
+    let policy = RetryPolicy::none().rate_limit(10.0);
+    let start = now();
+    for _ in 0..3 {
+        assert_eq!(get(&server).retry(policy.clone()).send().status_code(), 200);
+    }
+    // The first request goes out at once, the others 100ms apart.
+    assert!(now() - start >= 200.0);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[mochi_test]
+fn a_request_policy_overrides_the_default() {
+    let server = flaky(503, 1);
+    RetryPolicy::new().backoff(1).set_default();
+    assert_eq!(get(&server).send().status_code(), 200);
+    assert_eq!(server.requests().len(), 2);
+
+    let server = flaky(503, 1);
+    assert_eq!(get(&server).retry(RetryPolicy::none()).send().status_code(), 503);
+    assert_eq!(server.requests().len(), 1);
+
+    let server = flaky(503, 1);
+    RetryPolicy::clear_default();
+    assert_eq!(get(&server).send().status_code(), 503);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[mochi_test]
+fn retries_send_cookies_set_by_the_failed_response() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/flaky").body("ok"));
+    server.route(Route::get("/flaky").status(503).header("Set-Cookie", "challenge=passed").times(1));
+    let jar = CookieJar::new();
+    jar.set_cookie(&server.url("/"), "session=1");
+
+    let request = get(&server).cookie_jar(&jar).retry(RetryPolicy::new().backoff(1));
+    assert_eq!(request.send().status_code(), 200);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("cookie"), Some("session=1"));
+    assert_eq!(requests[1].header("cookie"), Some("session=1; challenge=passed"));
+}
@@ -0,0 +1,41 @@
+extern crate mochi_rs as mochi;
+
+use mochi::error::MochiError;
+use mochi::std::http::{Cache, Request, RequestMethod};
+use mochi::test::mochi_test;
+use mochi::test::server::{Route, Server};
+
+#[mochi_test]
+fn results_keep_the_order_of_the_requests() {
+    let server = Server::start().unwrap();
+    server.route(Route::get("/a").body("a"));
+    server.route(Route::get("/b").body("b"));
+    server.route(Route::get("/cached").body("cached"));
+    server.route(Route::get("/missing").status(404));
+    let unreachable = Server::start().unwrap().url("/gone");
+
+    let cached = || Request::new(server.url("/cached"), RequestMethod::Get).cache(Cache::new(60));
+    assert_eq!(cached().send().string().unwrap(), "cached");
+    assert_eq!(server.requests().len(), 1);
+
+    let results = Request::send_all(vec![
+        Request::new(server.url("/b"), RequestMethod::Get),
+        Request::new(&unreachable, RequestMethod::Get),
+        cached(),
+        Request::new(server.url("/missing"), RequestMethod::Get),
+        Request::new(server.url("/a"), RequestMethod::Get),
+    ]);
+    assert_eq!(results.len(), 5);
+
+    assert_eq!(results[0].as_ref().unwrap().string().unwrap(), "b");
+    assert_eq!(results[1].as_ref().unwrap_err(), &MochiError::Http { status: -1, url: unreachable });
+    assert_eq!(results[2].as_ref().unwrap().string().unwrap(), "cached");
+    // An error status is still a response.
+    assert_eq!(results[3].as_ref().unwrap().status_code(), 404);
+    assert_eq!(results[4].as_ref().unwrap().string().unwrap(), "a");
+
+    // The cached request wasn't sent again.
+    let mut paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    paths.sort();
+    assert_eq!(paths, ["/a", "/b", "/cached", "/missing"]);
+}
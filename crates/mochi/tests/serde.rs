@@ -0,0 +1,117 @@
+extern crate mochi_rs as mochi;
+
+use std::collections::BTreeMap;
+
+use mochi::std::serde::{from_ptr, to_ptr};
+use mochi::std::PtrRef;
+use mochi::test::{from_host, mochi_test, to_host, Value};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+enum Quality {
+    Auto,
+    Resolution(u32),
+    Range { low: u32, high: u32 },
+    Pair(String, bool),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Episode {
+    title: String,
+    episode_number: u16,
+    score: Option<f32>,
+    quality: Quality,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Season {
+    episodes: Vec<Episode>,
+    ranges: Vec<Vec<i64>>,
+    labels: BTreeMap<String, Option<String>>,
+    by_number: BTreeMap<u32, String>,
+}
+
+fn season() -> Season {
+    Season {
+        episodes: vec![
+            Episode { title: "One".into(), episode_number: 1, score: Some(7.5), quality: Quality::Auto },
+            Episode { title: "Two".into(), episode_number: 2, score: None, quality: Quality::Resolution(1080) },
+            Episode { title: "Three".into(), episode_number: 3, score: None, quality: Quality::Range { low: 360, high: 720 } },
+            Episode { title: "Four".into(), episode_number: 4, score: None, quality: Quality::Pair("hls".into(), true) },
+        ],
+        ranges: vec![vec![1, 2], vec![], vec![3, 4, 5]],
+        labels: BTreeMap::from([("sub".into(), Some("Subbed".into())), ("dub".into(), None)]),
+        by_number: BTreeMap::from([(1, "first".into()), (20, "twentieth".into())]),
+    }
+}
+
+fn round_trip<T: Serialize + for<'de> Deserialize<'de>>(value: &T) -> T {
+    from_ptr(to_ptr(value).unwrap()).unwrap()
+}
+
+#[mochi_test]
+fn structs_round_trip() {
+    let season = season();
+    assert_eq!(round_trip(&season), season);
+}
+
+#[mochi_test]
+fn enums_are_strings_or_single_key_objects() {
+    let value = to_host(to_ptr(&season().episodes).unwrap());
+    let quality = |i: usize| value.as_array().unwrap()[i].get("quality").unwrap().to_json();
+    assert_eq!(quality(0), serde_json::json!("Auto"));
+    assert_eq!(quality(1), serde_json::json!({ "Resolution": 1080 }));
+    assert_eq!(quality(2), serde_json::json!({ "Range": { "low": 360, "high": 720 } }));
+    assert_eq!(quality(3), serde_json::json!({ "Pair": ["hls", true] }));
+
+    for quality in [Quality::Auto, Quality::Resolution(480), Quality::Range { low: 1, high: 2 }] {
+        assert_eq!(round_trip(&quality), quality);
+    }
+}
+
+#[mochi_test]
+fn none_is_stored_as_a_host_null() {
+    let value = to_host(to_ptr(&vec![Some(1), None, Some(3)]).unwrap());
+    assert_eq!(value.to_json(), serde_json::json!([1, null, 3]));
+
+    let value = to_host(to_ptr(&season().labels).unwrap());
+    assert!(value.get("dub").is_some_and(Value::is_null));
+    assert_eq!(value.get("sub").and_then(Value::as_str), Some("Subbed"));
+
+    assert_eq!(round_trip(&vec![None, Some("a".to_string())]), vec![None, Some("a".to_string())]);
+}
+
+#[mochi_test]
+fn missing_keys_read_as_none() {
+    let value = serde_json::json!({
+        "title": "One",
+        "episodeNumber": 1,
+        "quality": "Auto",
+    });
+    let episode: Episode = from_ptr(from_host::<PtrRef>(value.into())).unwrap();
+    assert_eq!(episode.score, None);
+}
+
+#[mochi_test]
+fn map_keys_are_strings() {
+    let value = to_host(to_ptr(&season().by_number).unwrap());
+    assert_eq!(value.to_json(), serde_json::json!({ "1": "first", "20": "twentieth" }));
+}
+
+#[mochi_test]
+fn numbers_widen_and_narrow() {
+    let ptr = |value: serde_json::Value| from_host::<PtrRef>(value.into());
+
+    assert_eq!(from_ptr::<u8>(ptr(serde_json::json!(200))).unwrap(), 200);
+    assert_eq!(from_ptr::<i32>(ptr(serde_json::json!(-5))).unwrap(), -5);
+    assert_eq!(from_ptr::<f64>(ptr(serde_json::json!(3))).unwrap(), 3.0);
+    assert_eq!(from_ptr::<f32>(ptr(serde_json::json!(1.5))).unwrap(), 1.5);
+    assert!(from_ptr::<u8>(ptr(serde_json::json!(300))).is_err());
+    assert!(from_ptr::<u32>(ptr(serde_json::json!(-1))).is_err());
+    assert!(from_ptr::<i64>(ptr(serde_json::json!(1.5))).is_err());
+
+    assert!(matches!(to_host(to_ptr(&7u8).unwrap()), Value::Int(7)));
+    assert!(matches!(to_host(to_ptr(&1.5f32).unwrap()), Value::Float(f) if f == 1.5));
+    assert!(matches!(to_host(to_ptr(&u64::MAX).unwrap()), Value::Float(f) if f == u64::MAX as f64));
+}
@@ -0,0 +1,124 @@
+extern crate mochi_rs as mochi;
+
+use mochi::mochi_bind;
+use mochi::structs::settings::*;
+use mochi::test::{host, mochi_test, to_host};
+use serde_json::json;
+
+struct Source;
+
+#[mochi_bind]
+impl Settings for Source {
+    fn settings() -> SettingsSchema {
+        SettingsSchema(vec![
+            Setting::select("server", "Server", [("vidcloud", "VidCloud"), ("upcloud", "UpCloud")], "vidcloud"),
+            Setting::toggle("dub", "Prefer dub", true).description("Use dubbed episodes when there are some"),
+            Setting::text("language", "Subtitles", "English").placeholder("Language"),
+            Setting::number("quality", "Quality", 720.0).range(360.0, 1080.0, 360.0),
+            Setting::number("retries", "Retries", 3.0),
+        ])
+    }
+}
+
+fn pick<T: Into<mochi::test::Value>>(id: &str, value: T) {
+    host::with(|host| host.set_setting(id, value));
+}
+
+#[mochi_test]
+fn schema_is_exported_with_every_field() {
+    assert_eq!(
+        to_host(Source::settings()).to_json(),
+        json!([
+            {
+                "id": "server",
+                "title": "Server",
+                "type": "select",
+                "options": [
+                    { "id": "vidcloud", "displayName": "VidCloud" },
+                    { "id": "upcloud", "displayName": "UpCloud" },
+                ],
+                "default": "vidcloud",
+            },
+            {
+                "id": "dub",
+                "title": "Prefer dub",
+                "description": "Use dubbed episodes when there are some",
+                "type": "toggle",
+                "default": true,
+            },
+            {
+                "id": "language",
+                "title": "Subtitles",
+                "type": "text",
+                "default": "English",
+                "placeholder": "Language",
+            },
+            {
+                "id": "quality",
+                "title": "Quality",
+                "type": "number",
+                "default": 720.0,
+                "min": 360.0,
+                "max": 1080.0,
+                "step": 360.0,
+            },
+            {
+                "id": "retries",
+                "title": "Retries",
+                "type": "number",
+                "default": 3.0,
+            },
+        ])
+    );
+}
+
+#[mochi_test]
+fn defaults_are_used_until_the_user_picks_a_value() {
+    let settings = Source::settings();
+    assert_eq!(settings.select("server"), "vidcloud");
+    assert!(settings.toggle("dub"));
+    assert_eq!(settings.text("language"), "English");
+    assert_eq!(settings.number("quality"), 720.0);
+
+    pick("server", "upcloud");
+    pick("dub", false);
+    pick("language", "French");
+    pick("quality", 1080.0);
+    assert_eq!(settings.select("server"), "upcloud");
+    assert!(!settings.toggle("dub"));
+    assert_eq!(settings.text("language"), "French");
+    assert_eq!(settings.number("quality"), 1080.0);
+}
+
+#[mochi_test]
+fn unknown_settings_read_as_empty_values() {
+    let settings = Source::settings();
+    assert!(!settings.toggle("missing"));
+    assert_eq!(settings.select("missing"), "");
+    assert_eq!(settings.text("missing"), "");
+    assert_eq!(settings.number("missing"), 0.0);
+
+    // Values of the wrong type fall back to the default.
+    pick("dub", "yes");
+    assert!(settings.toggle("dub"));
+}
+
+#[mochi_test]
+fn numbers_are_clamped_to_their_range() {
+    let settings = Source::settings();
+    pick("quality", 4000.0);
+    assert_eq!(settings.number("quality"), 1080.0);
+    pick("quality", 100i64);
+    assert_eq!(settings.number("quality"), 360.0);
+
+    // Without a range, any value goes.
+    pick("retries", -2.0);
+    assert_eq!(settings.number("retries"), -2.0);
+}
+
+#[mochi_test]
+fn selects_fall_back_when_the_value_is_not_an_option() {
+    let settings = Source::settings();
+    pick("server", "streamtape");
+    assert_eq!(settings.select("server"), "vidcloud");
+}
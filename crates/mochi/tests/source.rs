@@ -0,0 +1,199 @@
+extern crate mochi_rs as mochi;
+
+use mochi::error::{Context, MochiError, Result};
+use mochi::std::http::RetryPolicy;
+use mochi::structs::meta::*;
+use mochi::structs::video::*;
+use mochi::test::{from_host, mochi_test, to_host, Value};
+use mochi::structs::source::Capability;
+use mochi::{mochi_bind, mochi_source};
+use serde_json::json;
+
+#[mochi_source(name = "Fake", id = "fake", version = "1.2.0", base_url = "https://fake.to")]
+struct Fake;
+
+#[mochi_bind]
+impl Meta for Fake {
+    fn search_filters() -> SearchFilters {
+        SearchFilters { filters: vec![] }
+    }
+
+    fn search(search_query: SearchQuery) -> Result<Paging<Playlist>> {
+        Ok(Paging {
+            id: search_query.page.unwrap_or_else(|| "1".into()),
+            previous_page: None,
+            next_page: Some("2".into()),
+            items: vec![Playlist {
+                id: format!("/watch/{}", search_query.query),
+                title: Some(search_query.query.to_uppercase()),
+                poster_image: Some("https://fake.to/poster.jpg".into()),
+                banner_image: None,
+                url: "https://fake.to/watch".into(),
+                status: PlaylistStatus::Ongoing,
+                playlist_type: PlaylistType::Video,
+            }],
+        })
+    }
+
+    fn discover_listings() -> Result<DiscoverListings> {
+        Ok(DiscoverListings(vec![]))
+    }
+
+    fn playlist_details(_id: String) -> Result<PlaylistDetails> {
+        Err(MochiError::Unimplemented)
+    }
+}
+
+#[mochi_bind]
+impl Video for Fake {
+    fn playlist_episodes(request: PlaylistItemsRequest) -> Result<PlaylistItemsResponse> {
+        let url = format!("https://fake.to/ajax/episodes/{}", request.playlist_id);
+        Err(MochiError::Http { status: 404, url })
+            .context("while fetching the episode list")
+            .context(format!("playlist {}", request.playlist_id))
+    }
+
+    fn playlist_episode_sources(request: PlaylistEpisodeSourcesRequest) -> Result<PlaylistEpisodeSources> {
+        Ok(PlaylistEpisodeSources(vec![PlaylistEpisodeSource {
+            id: format!("{}-sub", request.episode_id),
+            display_name: "Sub".into(),
+            description: None,
+            servers: vec![PlaylistEpisodeServer {
+                id: "vidcloud".into(),
+                display_name: "VidCloud".into(),
+                description: None,
+            }],
+        }]))
+    }
+
+    fn playlist_episode_server(request: PlaylistEpisodeServerRequest) -> Result<PlaylistEpisodeServerResponse> {
+        Ok(PlaylistEpisodeServerResponse {
+            links: vec![PlaylistEpisodeServerLink {
+                url: format!("https://cdn.fake.to/{}/master.m3u8", request.server_id),
+                quality: PlaylistEpisodeServerQualityType::Auto,
+                format_type: PlaylistEpisodeServerFormatType::HLS,
+            }],
+            subtitles: vec![],
+            skip_times: vec![],
+            headers: vec![PlaylistEpisodeServerHeader {
+                key: "Referer".into(),
+                value: "https://fake.to/".into(),
+            }],
+        })
+    }
+}
+
+#[mochi_source(name = "Listings")]
+struct MetaOnly;
+
+#[mochi_bind]
+impl Meta for MetaOnly {
+    fn search_filters() -> SearchFilters {
+        SearchFilters { filters: vec![] }
+    }
+
+    fn search(_search_query: SearchQuery) -> Result<Paging<Playlist>> {
+        Err(MochiError::Unimplemented)
+    }
+
+    fn discover_listings() -> Result<DiscoverListings> {
+        Ok(DiscoverListings(vec![]))
+    }
+
+    fn playlist_details(_id: String) -> Result<PlaylistDetails> {
+        Err(MochiError::Unimplemented)
+    }
+}
+
+#[mochi_test]
+fn source_info_lists_only_the_implemented_traits() {
+    let info = MetaOnly::source_info();
+    assert_eq!(info.name, "Listings");
+    assert_eq!(info.capabilities, [Capability::Meta]);
+
+    let info = Fake::source_info();
+    assert_eq!(info.capabilities, [Capability::Meta, Capability::Video]);
+    assert_eq!(
+        to_host(info).to_json(),
+        json!({
+            "id": "fake",
+            "name": "Fake",
+            "version": "1.2.0",
+            "baseUrl": "https://fake.to",
+            "nsfw": false,
+            "capabilities": ["meta", "video"],
+        })
+    );
+}
+
+#[mochi_test]
+fn search_returns_a_page_of_playlists() {
+    let query: SearchQuery = from_host(Value::from(json!({ "query": "naruto", "filters": [] })));
+    let paging = to_host(Fake::search(query));
+    assert_eq!(paging.get("id").and_then(Value::as_str), Some("1"));
+    assert_eq!(paging.get("nextPage").and_then(Value::as_str), Some("2"));
+    let items = paging.get("items").and_then(Value::as_array).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].get("id").and_then(Value::as_str), Some("/watch/naruto"));
+    assert_eq!(items[0].get("title").and_then(Value::as_str), Some("NARUTO"));
+}
+
+#[mochi_test]
+fn errors_reach_the_host() {
+    let error = to_host(Fake::playlist_details("x".into()));
+    assert_eq!(error.as_error(), Some((7, "not implemented")));
+    assert_eq!(
+        error.to_json(),
+        json!({ "error": 7, "message": "not implemented", "cause": "not implemented", "context": [] })
+    );
+}
+
+#[mochi_test]
+fn http_errors_reach_the_host_with_their_status_url_and_context() {
+    let request: PlaylistItemsRequest = from_host(Value::from(json!({ "playlistId": "naruto" })));
+    let error = to_host(Fake::playlist_episodes(request));
+    let url = "https://fake.to/ajax/episodes/naruto";
+    let cause = format!("request to {url} failed with status 404");
+    assert_eq!(
+        error.as_error(),
+        Some((6, format!("playlist naruto: while fetching the episode list: {cause}").as_str()))
+    );
+
+    let details = error.error_details().unwrap();
+    assert_eq!(details.get("status").and_then(Value::as_i64), Some(404));
+    assert_eq!(details.get("url").and_then(Value::as_str), Some(url));
+    assert_eq!(details.get("cause").and_then(Value::as_str), Some(cause.as_str()));
+    let context: Vec<_> = details["context"].as_array().unwrap().iter().filter_map(Value::as_str).collect();
+    assert_eq!(context, ["playlist naruto", "while fetching the episode list"]);
+}
+
+#[mochi_test]
+fn episode_sources_and_server() {
+    let request: PlaylistEpisodeSourcesRequest = from_host(Value::from(json!({ "playlistId": "p", "episodeId": "e1" })));
+    let sources = to_host(Fake::playlist_episode_sources(request));
+    let sources = sources.as_array().unwrap();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].get("id").and_then(Value::as_str), Some("e1-sub"));
+    let servers = sources[0].get("servers").and_then(Value::as_array).unwrap();
+    assert_eq!(servers[0].get("displayName").and_then(Value::as_str), Some("VidCloud"));
+
+    let request: PlaylistEpisodeServerRequest = from_host(Value::from(json!({
+        "playlistId": "p",
+        "episodeId": "e1",
+        "sourceId": "e1-sub",
+        "serverId": "vidcloud",
+    })));
+    let response = to_host(Fake::playlist_episode_server(request));
+    let links = response.get("links").and_then(Value::as_array).unwrap();
+    assert_eq!(links[0].get("url").and_then(Value::as_str), Some("https://cdn.fake.to/vidcloud/master.m3u8"));
+    let referer = response.get("headers").and_then(|headers| headers.get("Referer"));
+    assert_eq!(referer.and_then(Value::as_str), Some("https://fake.to/"));
+}
+
+#[mochi_test]
+fn reset_drops_library_state() {
+    RetryPolicy::new().max_retries(3).set_default();
+    assert!(RetryPolicy::default_policy().is_some());
+    mochi::test::reset();
+    assert!(RetryPolicy::default_policy().is_none());
+}
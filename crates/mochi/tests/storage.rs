@@ -0,0 +1,122 @@
+extern crate mochi_rs as mochi;
+
+use mochi::std::storage::{Key, Storage};
+use mochi::test::{host, mochi_test};
+
+const TOKEN: Key<String> = Key::new("token");
+const VISITS: Key<i64> = Key::new("visits");
+const HISTORY: Key<Vec<String>> = Key::new("history");
+
+#[mochi_test]
+fn values_are_read_back_as_they_were_stored() {
+    let storage = Storage::new();
+    assert_eq!(storage.get::<String>("token"), None);
+    assert!(!storage.contains("token"));
+
+    storage.set("token", "abc");
+    storage.set("count", 3i64);
+    storage.set("ratio", 0.5f64);
+    storage.set("enabled", true);
+    assert_eq!(storage.get::<String>("token").as_deref(), Some("abc"));
+    assert_eq!(storage.get_string("token").as_deref(), Some("abc"));
+    assert_eq!(storage.get::<i64>("count"), Some(3));
+    assert_eq!(storage.get::<f64>("ratio"), Some(0.5));
+    assert_eq!(storage.get::<bool>("enabled"), Some(true));
+
+    storage.set_string("token", "def");
+    assert_eq!(storage.get_string("token").as_deref(), Some("def"));
+    storage.remove("token");
+    assert_eq!(storage.get_string("token"), None);
+    assert_eq!(storage.keys(), ["count", "enabled", "ratio"]);
+}
+
+#[mochi_test]
+fn bytes_are_stored_as_is() {
+    let storage = Storage::new();
+    assert_eq!(storage.get_bytes("key"), None);
+    storage.set_bytes("key", &[0, 159, 146, 150, 255]);
+    assert_eq!(storage.get_bytes("key"), Some(vec![0, 159, 146, 150, 255]));
+    storage.set_bytes("empty", &[]);
+    assert_eq!(storage.get_bytes("empty"), Some(vec![]));
+
+    // Strings are read as their UTF-8 bytes.
+    storage.set_string("name", "mochi");
+    assert_eq!(storage.get_bytes("name"), Some(b"mochi".to_vec()));
+}
+
+#[mochi_test]
+fn scopes_prefix_their_keys() {
+    let storage = Storage::new();
+    let account = storage.scoped("account");
+    let cache = storage.scoped("cache");
+    let nested = account.scoped("session");
+
+    storage.set("token", "root");
+    account.set("token", "account");
+    nested.set("token", "session");
+    cache.set("token", "cache");
+
+    assert_eq!(storage.get_string("token").as_deref(), Some("root"));
+    assert_eq!(account.get_string("token").as_deref(), Some("account"));
+    assert_eq!(nested.get_string("token").as_deref(), Some("session"));
+    assert_eq!(cache.get_string("token").as_deref(), Some("cache"));
+    assert_eq!(storage.get_string("account/session/token").as_deref(), Some("session"));
+
+    assert_eq!(account.keys(), ["session/token", "token"]);
+    assert_eq!(nested.keys(), ["token"]);
+    assert_eq!(storage.keys(), ["account/session/token", "account/token", "cache/token", "token"]);
+}
+
+#[mochi_test]
+fn clear_only_removes_the_keys_of_its_scope() {
+    let storage = Storage::new();
+    let account = storage.scoped("account");
+    let accounts = storage.scoped("accounts");
+    let cache = storage.scoped("cache");
+    storage.set("token", "root");
+    account.set("token", "account");
+    account.scoped("session").set("id", 1i64);
+    accounts.set("token", "accounts");
+    cache.set("key", "cache");
+
+    account.clear();
+    assert!(account.keys().is_empty());
+    assert_eq!(storage.keys(), ["accounts/token", "cache/key", "token"]);
+    assert_eq!(accounts.get_string("token").as_deref(), Some("accounts"));
+    assert_eq!(cache.get_string("key").as_deref(), Some("cache"));
+
+    storage.clear();
+    assert!(storage.keys().is_empty());
+}
+
+#[mochi_test]
+fn each_source_has_its_own_storage() {
+    Storage::new().set("token", "first");
+    host::with(|host| host.set_source("other"));
+    assert_eq!(Storage::new().get_string("token"), None);
+    Storage::new().set("token", "second");
+
+    host::with(|host| host.set_source(""));
+    assert_eq!(Storage::new().get_string("token").as_deref(), Some("first"));
+}
+
+#[mochi_test]
+fn typed_keys_convert_their_values() {
+    assert_eq!(TOKEN.get(), None);
+    TOKEN.set("abc".to_string());
+    VISITS.set(2);
+    HISTORY.set(vec!["one".to_string(), "two".to_string()]);
+    assert_eq!(TOKEN.get().as_deref(), Some("abc"));
+    assert_eq!(VISITS.get(), Some(2));
+    assert_eq!(HISTORY.get(), Some(vec!["one".to_string(), "two".to_string()]));
+    assert_eq!(Storage::new().get_string(TOKEN.name()).as_deref(), Some("abc"));
+
+    let scope = Storage::new().scoped("account");
+    TOKEN.set_in(&scope, "scoped".to_string());
+    assert_eq!(TOKEN.get_in(&scope).as_deref(), Some("scoped"));
+    assert_eq!(TOKEN.get().as_deref(), Some("abc"));
+
+    TOKEN.remove();
+    assert_eq!(TOKEN.get(), None);
+    assert_eq!(TOKEN.get_in(&scope).as_deref(), Some("scoped"));
+}
@@ -0,0 +1,11 @@
+extern crate mochi_rs as mochi;
+
+use mochi::FromHost;
+
+#[derive(FromHost)]
+enum Quality {
+    Auto,
+    Hd,
+}
+
+fn main() {}
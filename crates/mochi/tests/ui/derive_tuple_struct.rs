@@ -0,0 +1,8 @@
+extern crate mochi_rs as mochi;
+
+use mochi::IntoHost;
+
+#[derive(IntoHost)]
+struct Episode(i64, String);
+
+fn main() {}
@@ -0,0 +1,31 @@
+// The impl is replaced by the errors, leaving the imports unused.
+#![allow(unused_imports)]
+
+extern crate mochi_rs as mochi;
+
+use mochi::error::Result;
+use mochi::mochi_bind;
+use mochi::structs::meta::*;
+
+struct Source;
+
+#[mochi_bind]
+impl Meta for Source {
+    fn search_filters() -> SearchFilters {
+        SearchFilters { filters: vec![] }
+    }
+
+    fn serch(_search_query: SearchQuery) -> Result<Paging<Playlist>> {
+        todo!()
+    }
+
+    fn discover_listings() -> Result<DiscoverListings> {
+        todo!()
+    }
+
+    fn playlist_details(_id: String) -> Result<PlaylistDetails> {
+        todo!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+extern crate mochi_rs as mochi;
+
+use mochi::mochi_bind;
+use mochi::structs::settings::*;
+
+struct Source;
+
+#[mochi_bind]
+impl Settings for Source {
+    fn settings() -> SettingsSchema {
+        SettingsSchema(vec![
+            Setting::select("server", "Server", [("vidcloud", "VidCloud"), ("upcloud", "UpCloud")], "vidcloud"),
+            Setting::toggle("dub", "Prefer dub", false).description("Use dubbed episodes when there are some"),
+            Setting::text("language", "Subtitles", "English").placeholder("Language"),
+            Setting::number("quality", "Quality", 720.0).range(360.0, 1080.0, 360.0),
+        ])
+    }
+}
+
+fn main() {
+    let _ = Source::settings().select("server");
+}